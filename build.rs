@@ -0,0 +1,107 @@
+//! Generates the day registry from the modules on disk, so that adding a day
+//! is just a matter of creating its directory.
+//!
+//! Days for 2024 live in `src/dayNN`, and days for any other year in
+//! `src/yearYYYY/dayNN`. Each `mod.rs` must define a `DayNN` type
+//! implementing `Solution`.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+const DEFAULT_YEAR: usize = 2024;
+
+/// Finds the `dayNN` directories containing a `mod.rs` under `dir`.
+fn find_days(dir: &Path) -> Vec<(usize, PathBuf)> {
+    let mut days = numbered_entries(dir, "day")
+        .into_iter()
+        .filter(|(_, path)| path.join("mod.rs").is_file())
+        .collect::<Vec<_>>();
+    days.sort();
+
+    days
+}
+
+fn numbered_entries(dir: &Path, prefix: &str) -> Vec<(usize, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().into_string().ok()?;
+            let number = name.strip_prefix(prefix)?.parse().ok()?;
+
+            entry
+                .file_type()
+                .ok()?
+                .is_dir()
+                .then(|| (number, entry.path()))
+        })
+        .collect()
+}
+
+fn module(name: &str, path: &Path) -> String {
+    format!(
+        "#[path = {:?}]\npub mod {};\n",
+        path.join("mod.rs").display().to_string(),
+        name
+    )
+}
+
+fn main() {
+    let src = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    println!("cargo:rerun-if-changed=src");
+    // `debug` or `release`, so that `watch` can rebuild with the same profile.
+    println!(
+        "cargo:rustc-env=BUILD_PROFILE={}",
+        env::var("PROFILE").unwrap()
+    );
+
+    let mut years = numbered_entries(&src, "year")
+        .into_iter()
+        .filter(|(year, _)| *year != DEFAULT_YEAR)
+        .map(|(year, path)| (year, find_days(&path)))
+        .collect::<Vec<_>>();
+    years.push((DEFAULT_YEAR, find_days(&src)));
+    years.sort();
+
+    let mut modules = String::new();
+    let mut entries = vec![];
+
+    for (year, days) in &years {
+        let parent = if *year == DEFAULT_YEAR {
+            String::new()
+        } else {
+            format!("year{}::", year)
+        };
+
+        let mut year_modules = String::new();
+        for (day, path) in days {
+            let name = format!("day{:02}", day);
+            year_modules += &module(&name, path);
+            entries.push(format!(
+                "    Day::new::<{}{}::Day{:02}>({}),\n",
+                parent, name, day, year
+            ));
+        }
+
+        if *year == DEFAULT_YEAR {
+            modules += &year_modules;
+        } else if !days.is_empty() {
+            modules += &format!("pub mod year{} {{\n{}}}\n", year, year_modules);
+        }
+    }
+
+    let generated = format!(
+        "{}\n/// Every implemented day, ordered by year and then day.\npub static DAYS: [Day; {}] = [\n{}];\n",
+        modules,
+        entries.len(),
+        entries.concat()
+    );
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out, generated).unwrap();
+}
@@ -0,0 +1,128 @@
+//! Heap profiling for the `alloc-profile` feature, which installs [`Counting`]
+//! as the global allocator.
+//!
+//! Counts are kept per thread, so days running side by side with `--jobs`
+//! don't see each other's allocations.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Heap usage while running one stage of a day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// The most the heap grew by at any point.
+    pub peak_bytes: usize,
+    pub total_bytes: usize,
+    pub count: usize,
+}
+
+thread_local! {
+    static CURRENT: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+    static TOTAL: Cell<usize> = const { Cell::new(0) };
+    static COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Wraps the system allocator, counting what each thread allocates.
+pub struct Counting;
+
+fn allocated(size: usize) {
+    // `try_with` rather than `with`, since allocations can still happen while
+    // a thread's locals are being torn down.
+    let _ = CURRENT.try_with(|current| {
+        current.set(current.get() + size);
+        PEAK.with(|peak| peak.set(peak.get().max(current.get())));
+    });
+    let _ = TOTAL.try_with(|total| total.set(total.get() + size));
+    let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+}
+
+fn freed(size: usize) {
+    let _ = CURRENT.try_with(|current| current.set(current.get().saturating_sub(size)));
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+
+        new
+    }
+}
+
+#[cfg(feature = "alloc-profile")]
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Runs `f`, counting its allocations on this thread. Without the
+/// `alloc-profile` feature nothing is counted, and the stats are `None`.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !cfg!(feature = "alloc-profile") {
+        return (f(), None);
+    }
+
+    let start = CURRENT.get();
+    let total = TOTAL.get();
+    let count = COUNT.get();
+    PEAK.set(start);
+
+    let result = f();
+
+    let stats = AllocStats {
+        peak_bytes: PEAK.get() - start,
+        total_bytes: TOTAL.get() - total,
+        count: COUNT.get() - count,
+    };
+
+    (result, Some(stats))
+}
+
+#[cfg(all(test, feature = "alloc-profile"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_allocations() {
+        let (_, stats) = measure(|| {
+            let a = vec![0u8; 1000];
+            let b = vec![0u8; 500];
+            drop(a);
+            drop(b);
+
+            vec![0u8; 200]
+        });
+        let stats = stats.unwrap();
+
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.total_bytes, 1700);
+        assert_eq!(stats.peak_bytes, 1500);
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{runner::Report, solution::Answer};
+
+/// Known-good answers for a day, kept in `answers.toml` next to its input.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+pub fn answers_path(input_path: &Path) -> PathBuf {
+    input_path.with_file_name("answers.toml")
+}
+
+impl Answers {
+    /// Loads the answers file, treating a missing file as having no answers.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                toml::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let contents =
+            toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        fs::write(path, contents)
+    }
+
+    pub fn from_report(report: &Report) -> Self {
+        Answers {
+            part1: report.part1.solved(),
+            part2: report.part2.solved(),
+        }
+    }
+
+    pub fn verify(&self, report: &Report) -> [Verdict; 2] {
+        [
+            Verdict::of(self.part1.as_deref(), &report.part1),
+            Verdict::of(self.part2.as_deref(), &report.part2),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// There is no known answer to check against.
+    Missing,
+    /// The part has no solution, and no answer was expected.
+    Unsolved,
+}
+
+impl Verdict {
+    fn of(expected: Option<&str>, actual: &Answer) -> Self {
+        match (expected, actual) {
+            (None, Answer::Unsolved) => Verdict::Unsolved,
+            (None, _) => Verdict::Missing,
+            (Some(expected), actual) if expected == actual.to_string() => Verdict::Pass,
+            (Some(expected), actual) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Verdict::Missing => write!(f, "missing"),
+            Verdict::Unsolved => write!(f, "-"),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{parse::ParseError, solution::Solution};
+
+/// How long to keep running a stage before sampling starts, so that caches
+/// and the allocator have settled.
+const WARMUP: Duration = Duration::from_millis(200);
+
+/// Slow stages stop sampling after this long, as long as they have at least
+/// `MIN_SAMPLES` samples.
+const BUDGET: Duration = Duration::from_secs(3);
+const MIN_SAMPLES: usize = 5;
+
+/// Summary statistics over the samples taken for one stage.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        let n = samples.len();
+        // Nearest-rank percentile: the smallest sample that at least `p`% of
+        // samples are less than or equal to.
+        let percentile = |p: usize| samples[(n * p).div_ceil(100).max(1) - 1];
+
+        Stats {
+            samples: n,
+            min: samples[0],
+            median: percentile(50),
+            p95: percentile(95),
+            mean: samples.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl BenchReport {
+    pub fn stages(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("parse", &self.parse),
+            ("part 1", &self.part1),
+            ("part 2", &self.part2),
+        ]
+    }
+}
+
+fn sample<T>(iterations: usize, mut f: impl FnMut() -> T) -> Stats {
+    let warmup = Instant::now();
+    loop {
+        f();
+        if warmup.elapsed() >= WARMUP {
+            break;
+        }
+    }
+
+    let start = Instant::now();
+    let mut samples = Vec::with_capacity(iterations);
+    while samples.len() < iterations {
+        let run = Instant::now();
+        let res = f();
+        samples.push(run.elapsed());
+        drop(res);
+
+        if samples.len() >= MIN_SAMPLES && start.elapsed() >= BUDGET {
+            break;
+        }
+    }
+
+    Stats::from_samples(samples)
+}
+
+/// Times parsing and each part separately, up to `iterations` times each.
+pub fn bench<S: Solution>(input: &str, iterations: usize) -> Result<BenchReport, ParseError> {
+    let parsed = S::parse(input).map_err(|e| e.for_day(S::DAY))?;
+
+    let parse = sample(iterations, || S::parse(input));
+    let part1 = sample(iterations, || S::part1(&parsed));
+    let part2 = sample(iterations, || S::part2(&parsed));
+
+    Ok(BenchReport {
+        parse,
+        part1,
+        part2,
+    })
+}
+
+/// Bench reports keyed by `YYYY/NN`, either from the latest run or saved as
+/// the baseline that runs are compared against.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BenchResults(BTreeMap<String, BenchReport>);
+
+/// Where the latest run of each day is saved.
+pub fn bench_results_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("bench-results.json")
+}
+
+/// Where the baseline is saved, which only changes when asked to.
+pub fn bench_baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("bench-baseline.json")
+}
+
+impl BenchResults {
+    /// Loads earlier results, treating a missing file as having none.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(BenchResults::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        fs::write(path, contents)
+    }
+
+    pub fn get(&self, year: usize, day: usize) -> Option<&BenchReport> {
+        self.0.get(&Self::key(year, day))
+    }
+
+    pub fn insert(&mut self, year: usize, day: usize, report: BenchReport) {
+        self.0.insert(Self::key(year, day), report);
+    }
+
+    fn key(year: usize, day: usize) -> String {
+        format!("{}/{:02}", year, day)
+    }
+}
+
+/// The relative change in median time from `before` to `after`, as a
+/// percentage.
+pub fn change(before: &Stats, after: &Stats) -> f64 {
+    let before = before.median.as_secs_f64();
+    let after = after.median.as_secs_f64();
+
+    if before == 0.0 {
+        0.0
+    } else {
+        (after - before) / before * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_micros(v)).collect()
+    }
+
+    #[test]
+    fn summarises_samples() {
+        let stats = Stats::from_samples(micros(&[5, 1, 4, 2, 3]));
+
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.mean, Duration::from_micros(3));
+    }
+
+    #[test]
+    fn takes_nearest_rank_percentiles() {
+        let stats = Stats::from_samples(micros(&(1..=100).collect::<Vec<_>>()));
+
+        assert_eq!(stats.median, Duration::from_micros(50));
+        assert_eq!(stats.p95, Duration::from_micros(95));
+
+        let stats = Stats::from_samples(micros(&[7]));
+
+        assert_eq!(stats.median, Duration::from_micros(7));
+        assert_eq!(stats.p95, Duration::from_micros(7));
+    }
+}
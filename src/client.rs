@@ -0,0 +1,239 @@
+use std::{
+    cell::Cell,
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+
+use crate::submit::Outcome;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Sent with every request, so the puzzle server's operators know where the
+/// traffic comes from.
+pub const USER_AGENT: &str = concat!(
+    "github.com/reidswan/aoc2024 ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// The least time left between two requests from the same client.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where to find the puzzle server, and the session token to use with it.
+///
+/// Read from `aoc.toml` in the crate root, with the `AOC_BASE_URL` and
+/// `AOC_SESSION` environment variables taking precedence.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+pub struct Config {
+    pub base_url: Option<String>,
+    pub session: Option<String>,
+}
+
+pub fn config_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("aoc.toml")
+}
+
+impl Config {
+    /// Loads the config file, treating a missing file as empty, then applies
+    /// any overrides from the environment.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut config: Config = match fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(e) => return Err(e),
+        };
+
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = Some(base_url);
+        }
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+
+        Ok(config)
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// There is no session token in the config file or the environment.
+    MissingSession,
+    /// The server asked us to back off, for this many seconds if it said.
+    RateLimited(Option<u64>),
+    /// The server answered with an unexpected status code.
+    Status(u16, String),
+    /// The request didn't get a response at all.
+    Transport(String),
+    /// The server responded, but not in a way we understood.
+    Unrecognised(String),
+    Io(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session token: set AOC_SESSION or `session` in {}",
+                config_path().display()
+            ),
+            Error::RateLimited(Some(seconds)) => {
+                write!(f, "rate limited, try again in {} seconds", seconds)
+            }
+            Error::RateLimited(None) => write!(f, "rate limited, try again later"),
+            Error::Status(code, body) => write!(f, "server responded {}: {}", code, body.trim()),
+            Error::Transport(e) => write!(f, "request failed: {}", e),
+            Error::Unrecognised(body) => write!(f, "unrecognised response: {}", body.trim()),
+            Error::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::Io(value)
+    }
+}
+
+/// Whether [`Client::fetch_input`] had to go to the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// A client for the puzzle server, which spaces its requests out by at least
+/// [`MIN_INTERVAL`].
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Result<Self, Error> {
+        let session = config.session.ok_or(Error::MissingSession)?;
+        let base_url = config
+            .base_url
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+
+        Ok(Client {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            min_interval: MIN_INTERVAL,
+            last_request: Cell::new(None),
+        })
+    }
+
+    pub fn with_min_interval(self, min_interval: Duration) -> Self {
+        Client {
+            min_interval,
+            ..self
+        }
+    }
+
+    /// Downloads a day's input.
+    pub fn input(&self, year: usize, day: usize) -> Result<String, Error> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+
+        self.send(self.agent.get(&url), None)
+    }
+
+    /// Submits an answer to one part of a day. Being told to wait before
+    /// answering again is reported as [`Error::RateLimited`], since the answer
+    /// wasn't checked.
+    pub fn submit(
+        &self,
+        year: usize,
+        day: usize,
+        part: usize,
+        answer: &str,
+    ) -> Result<Outcome, Error> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = part.to_string();
+        let response = self.send(
+            self.agent.post(&url),
+            Some(&[("level", &level), ("answer", answer)]),
+        )?;
+
+        if response.contains("You gave an answer too recently") {
+            return Err(Error::RateLimited(wait_time(&response)));
+        }
+
+        Outcome::parse(&response).ok_or(Error::Unrecognised(response))
+    }
+
+    /// Writes a day's input to `path`, unless it is already there. An empty
+    /// file, as left by `new`, doesn't count.
+    pub fn fetch_input(&self, year: usize, day: usize, path: &Path) -> Result<Fetched, Error> {
+        if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+            return Ok(Fetched::Cached);
+        }
+
+        let input = self.input(year, day)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, input)?;
+
+        Ok(Fetched::Downloaded)
+    }
+
+    fn send(&self, request: ureq::Request, form: Option<&[(&str, &str)]>) -> Result<String, Error> {
+        if let Some(last) = self.last_request.get() {
+            thread::sleep(self.min_interval.saturating_sub(last.elapsed()));
+        }
+        self.last_request.set(Some(Instant::now()));
+
+        let request = request.set("Cookie", &format!("session={}", self.session));
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(429, response)) => Err(Error::RateLimited(
+                response
+                    .header("Retry-After")
+                    .and_then(|seconds| seconds.parse().ok()),
+            )),
+            Err(ureq::Error::Status(code, response)) => Err(Error::Status(
+                code,
+                response.into_string().unwrap_or_default(),
+            )),
+            Err(e) => Err(Error::Transport(e.to_string())),
+        }
+    }
+}
+
+/// Reads how long to wait from e.g. "You have 1m 5s left to wait".
+fn wait_time(response: &str) -> Option<u64> {
+    let (before, _) = response.split_once(" left to wait")?;
+    let (_, time) = before.rsplit_once("You have ")?;
+
+    time.split_whitespace().try_fold(0, |total, part| {
+        let (number, unit) = part.split_at(part.len() - 1);
+        let number = number.parse::<u64>().ok()?;
+        match unit {
+            "m" => Some(total + number * 60),
+            "s" => Some(total + number),
+            _ => None,
+        }
+    })
+}
@@ -1,51 +1,100 @@
-use std::collections::HashMap;
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+    util::Counter,
+};
 
-const INPUT: &'static str = include_str!("./input.in");
+pub struct Day01;
 
-pub fn run() {
-    let (mut l1, mut l2) = parse_input(INPUT);
+impl Solution for Day01 {
+    const DAY: usize = 1;
+    type Input<'a> = (Vec<isize>, Vec<isize>);
 
-    l1.sort();
-    l2.sort();
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((l1, l2): &Self::Input<'_>) -> Answer {
+        let mut l1 = l1.clone();
+        let mut l2 = l2.clone();
+        l1.sort();
+        l2.sort();
 
-    println!(
-        "Part 1: {}",
-        &l1.iter()
+        l1.iter()
             .zip(&l2)
             .map(|(&a, &b)| (a - b).abs())
             .sum::<isize>()
-    );
+            .into()
+    }
 
-    let counts = count_occurrences(&l2);
+    fn part2((l1, l2): &Self::Input<'_>) -> Answer {
+        let counts = count_occurrences(l2);
 
-    println!(
-        "Part 2: {}",
-        &l1.iter()
-            .map(|i| *i * (*counts.get(i).unwrap_or(&0) as isize))
+        l1.iter()
+            .map(|i| *i * counts.get(i) as isize)
             .sum::<isize>()
-    )
+            .into()
+    }
 }
 
-fn parse_input(input: &str) -> (Vec<isize>, Vec<isize>) {
+fn parse_input(input: &str) -> Result<(Vec<isize>, Vec<isize>), ParseError> {
     let mut l1: Vec<isize> = Vec::new();
     let mut l2: Vec<isize> = Vec::new();
 
     for line in input.lines() {
-        let mut splits = line.split(' ');
-        l1.push(splits.next().and_then(|i| i.parse().ok()).unwrap());
-        l2.push(splits.last().and_then(|i| i.parse().ok()).unwrap());
+        let mut splits = line.split_ascii_whitespace();
+        l1.push(parse::number(
+            input,
+            parse::next(input, line, &mut splits, "a number")?,
+        )?);
+        l2.push(parse::number(
+            input,
+            parse::next(input, line, &mut splits, "a second number")?,
+        )?);
     }
 
-    (l1, l2)
+    Ok((l1, l2))
 }
 
-fn count_occurrences(l: &[isize]) -> HashMap<isize, usize> {
-    let mut res = HashMap::new();
-    for i in l {
-        res.entry(*i)
-            .and_modify(|count| *count = *count + 1)
-            .or_insert(1);
+fn count_occurrences(l: &[isize]) -> Counter<isize> {
+    l.iter().copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+    #[test]
+    fn parses_both_lists() {
+        assert_eq!(
+            parse_input(EXAMPLE).unwrap(),
+            (vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3])
+        );
+    }
+
+    #[test]
+    fn counts_occurrences() {
+        let counts = count_occurrences(&[4, 3, 5, 3, 9, 3]);
+
+        assert_eq!(counts.get(&3), 3);
+        assert_eq!(counts.get(&4), 1);
+        assert_eq!(counts.get(&1), 0);
     }
 
-    res
+    #[test]
+    fn solves_example() {
+        let input = Day01::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day01::part1(&input), Answer::Number(11));
+        assert_eq!(Day01::part2(&input), Answer::Number(31));
+    }
 }
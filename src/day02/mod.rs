@@ -1,30 +1,41 @@
-use std::isize;
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+};
 
-const INPUT: &'static str = include_str!("./input.in");
+pub struct Day02;
 
-pub fn run() {
-    let reports = parse_input(INPUT);
+impl Solution for Day02 {
+    const DAY: usize = 2;
+    type Input<'a> = Vec<Vec<isize>>;
 
-    println!(
-        "Part 1: {}",
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(reports: &Self::Input<'_>) -> Answer {
         reports
             .iter()
-            .filter(|r| check_safety(r.iter().map(|e| *e)))
+            .filter(|r| check_safety(r.iter().copied()))
             .count()
-    );
+            .into()
+    }
 
-    println!(
-        "Part 2: {}",
-        reports.iter().filter(|r| check_safety_part2(r)).count()
-    );
+    fn part2(reports: &Self::Input<'_>) -> Answer {
+        reports
+            .iter()
+            .filter(|r| check_safety_part2(r))
+            .count()
+            .into()
+    }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<isize>> {
+fn parse_input(input: &str) -> Result<Vec<Vec<isize>>, ParseError> {
     input
         .lines()
         .map(|e| {
             e.split_ascii_whitespace()
-                .map(|e| e.parse().unwrap())
+                .map(|e| parse::number(input, e))
                 .collect()
         })
         .collect()
@@ -55,10 +66,54 @@ fn check_safety_part2(report: &[isize]) -> bool {
     for i in 0..report.len() {
         let iter = report.iter().take(i).chain(report.iter().skip(i + 1));
 
-        if check_safety(iter.map(|e| *e)) {
+        if check_safety(iter.copied()) {
             return true;
         }
     }
 
-    return false;
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+
+    #[test]
+    fn checks_safety() {
+        let reports = parse_input(EXAMPLE).unwrap();
+        let safe = reports
+            .iter()
+            .map(|r| check_safety(r.iter().copied()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(safe, [true, false, false, false, false, true]);
+    }
+
+    #[test]
+    fn checks_safety_with_problem_dampener() {
+        let reports = parse_input(EXAMPLE).unwrap();
+        let safe = reports
+            .iter()
+            .map(|r| check_safety_part2(r))
+            .collect::<Vec<_>>();
+
+        assert_eq!(safe, [true, false, false, true, true, true]);
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day02::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day02::part1(&input), Answer::Number(2));
+        assert_eq!(Day02::part2(&input), Answer::Number(4));
+    }
 }
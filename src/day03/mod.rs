@@ -1,11 +1,25 @@
-use regex;
+use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
 
-const INPUT: &'static str = include_str!("./input.in");
+pub struct Day03;
 
-pub fn run() {
-    println!("Part 1: {}", run_part_1(INPUT));
+impl Solution for Day03 {
+    const DAY: usize = 3;
+    type Input<'a> = &'a str;
 
-    println!("Part 2: {}", run_part_2(INPUT));
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        run_part_1(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        run_part_2(input).into()
+    }
 }
 
 fn run_part_1(input: &str) -> usize {
@@ -25,6 +39,25 @@ fn run_part_2(input: &str) -> usize {
     input
         .split("do()")
         .map(|section| section.split("don't()").next().unwrap())
-        .map(|section| run_part_1(section))
+        .map(run_part_1)
         .sum::<usize>()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums_multiplications() {
+        let example = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+        assert_eq!(run_part_1(example), 161);
+    }
+
+    #[test]
+    fn respects_do_and_dont() {
+        let example = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+        assert_eq!(run_part_2(example), 48);
+    }
+}
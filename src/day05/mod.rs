@@ -1,68 +1,86 @@
 use std::collections::{HashMap, HashSet};
 
-const INPUT: &'static str = include_str!("./input.in");
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+};
 
-pub fn run() {
-    let (rules, updates) = parse_input(INPUT);
-    let rule_graph = gen_rule_graph(&rules);
+pub struct Day05;
 
-    let (compliant, non_compliant) = updates.iter().partition::<Vec<_>, _>(|update| {
-        for i in 0..update.len() {
-            let elem = update[i];
-            let Some(must_after) = rule_graph.get(&elem) else {
-                continue;
-            };
+impl Solution for Day05 {
+    const DAY: usize = 5;
+    type Input<'a> = (Vec<Rule>, Vec<Update>);
 
-            for after in &update[i + 1..] {
-                if !must_after.contains(after) {
-                    return false;
-                }
-            }
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((rules, updates): &Self::Input<'_>) -> Answer {
+        let rule_graph = gen_rule_graph(rules);
 
-        true
-    });
+        updates
+            .iter()
+            .filter(|update| is_compliant(update, &rule_graph))
+            .map(|c| c[c.len() / 2])
+            .sum::<usize>()
+            .into()
+    }
 
-    println!(
-        "Part 1: {}",
-        compliant.iter().map(|c| c[c.len() / 2]).sum::<usize>()
-    );
+    fn part2((rules, updates): &Self::Input<'_>) -> Answer {
+        let rule_graph = gen_rule_graph(rules);
 
-    println!(
-        "Part 2: {}",
-        non_compliant
+        updates
             .iter()
+            .filter(|update| !is_compliant(update, &rule_graph))
             .map(|c| {
                 let fixed = fix_non_compliant(c, &rule_graph);
 
                 fixed[fixed.len() / 2]
             })
             .sum::<usize>()
-    );
+            .into()
+    }
+}
+
+fn is_compliant(update: &[usize], rule_graph: &RuleGraph) -> bool {
+    for i in 0..update.len() {
+        let elem = update[i];
+        let Some(must_after) = rule_graph.get(&elem) else {
+            continue;
+        };
+
+        for after in &update[i + 1..] {
+            if !must_after.contains(after) {
+                return false;
+            }
+        }
+    }
+
+    true
 }
 
 type Rule = (usize, usize);
 type Update = Vec<usize>;
 type RuleGraph = HashMap<usize, HashSet<usize>>;
 
-fn parse_input(input: &str) -> (Vec<Rule>, Vec<Update>) {
-    let (rules_src, updates_src) = input.split_once("\n\n").unwrap();
+fn parse_input(input: &str) -> Result<(Vec<Rule>, Vec<Update>), ParseError> {
+    let (rules_src, updates_src) = parse::split_once(input, input, "\n\n")?;
 
     let rules = rules_src
         .lines()
         .map(|r| {
-            let mut elems = r.split("|").map(|i| i.parse::<usize>().unwrap());
+            let (before, after) = parse::split_once(input, r, "|")?;
 
-            (elems.next().unwrap(), elems.next().unwrap())
+            Ok((parse::number(input, before)?, parse::number(input, after)?))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     let updates = updates_src
         .lines()
-        .map(|line| line.split(',').map(|it| it.parse().unwrap()).collect())
-        .collect();
+        .map(|line| line.split(',').map(|it| parse::number(input, it)).collect())
+        .collect::<Result<_, _>>()?;
 
-    (rules, updates)
+    Ok((rules, updates))
 }
 
 fn gen_rule_graph(rules: &[Rule]) -> RuleGraph {
@@ -81,7 +99,7 @@ fn gen_rule_graph(rules: &[Rule]) -> RuleGraph {
 fn fix_non_compliant(update: &[usize], rule_graph: &RuleGraph) -> Vec<usize> {
     // Yay topological sort
 
-    let mut pending_elems = HashSet::from_iter(update.iter().map(|i| *i));
+    let mut pending_elems = HashSet::from_iter(update.iter().copied());
     let mut ord = Vec::<usize>::new();
     let mut no_after_requirement = pending_elems
         .iter()
@@ -91,11 +109,10 @@ fn fix_non_compliant(update: &[usize], rule_graph: &RuleGraph) -> Vec<usize> {
             };
             afters.intersection(&pending_elems).count() == 0
         })
-        .map(|it| *it)
+        .copied()
         .collect::<Vec<_>>();
 
-    while no_after_requirement.len() > 0 {
-        let curr = no_after_requirement.pop().unwrap();
+    while let Some(curr) = no_after_requirement.pop() {
         pending_elems.remove(&curr);
         ord.push(curr);
 
@@ -107,7 +124,7 @@ fn fix_non_compliant(update: &[usize], rule_graph: &RuleGraph) -> Vec<usize> {
                 };
                 afters.intersection(&pending_elems).count() == 0
             })
-            .map(|it| *it)
+            .copied()
             .collect::<Vec<_>>();
     }
 
@@ -115,3 +132,78 @@ fn fix_non_compliant(update: &[usize], rule_graph: &RuleGraph) -> Vec<usize> {
 
     ord
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+
+    #[test]
+    fn parses_rules_and_updates() {
+        let (rules, updates) = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(rules.len(), 21);
+        assert_eq!(rules[0], (47, 53));
+        assert_eq!(updates.len(), 6);
+        assert_eq!(updates[2], vec![75, 29, 13]);
+    }
+
+    #[test]
+    fn fixes_ordering() {
+        let (rules, _) = parse_input(EXAMPLE).unwrap();
+        let rule_graph = gen_rule_graph(&rules);
+
+        assert!(is_compliant(&[75, 47, 61, 53, 29], &rule_graph));
+        assert!(!is_compliant(&[75, 97, 47, 61, 53], &rule_graph));
+        assert_eq!(
+            fix_non_compliant(&[75, 97, 47, 61, 53], &rule_graph),
+            vec![97, 75, 47, 61, 53]
+        );
+        assert_eq!(
+            fix_non_compliant(&[61, 13, 29], &rule_graph),
+            vec![61, 29, 13]
+        );
+        assert_eq!(
+            fix_non_compliant(&[97, 13, 75, 29, 47], &rule_graph),
+            vec![97, 75, 47, 29, 13]
+        );
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day05::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day05::part1(&input), Answer::Number(143));
+        assert_eq!(Day05::part2(&input), Answer::Number(123));
+    }
+}
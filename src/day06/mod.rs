@@ -1,90 +1,66 @@
 use std::collections::HashSet;
 
-const INPUT: &'static str = include_str!("./input.in");
+use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+    util::{Coord, Direction, Grid, Tile},
+};
 
-pub fn run() {
-    let (mut map, guard_loc) = parse_input(INPUT);
-    let init_direction = (0, -1);
-    let visited = trace_path(&map, guard_loc, init_direction);
+pub struct Day06;
 
-    println!("Part 1: {}", visited.len());
+impl Solution for Day06 {
+    const DAY: usize = 6;
+    type Input<'a> = (Grid<Slot>, Coord);
 
-    println!(
-        "Part 2: {}",
-        make_loops(&mut map, guard_loc, init_direction, &visited)
-    )
-}
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
 
-#[derive(PartialEq, Eq)]
-enum Slot {
-    Empty,
-    Obstacle,
-}
+    fn part1((map, guard_loc): &Self::Input<'_>) -> Answer {
+        trace_path(map, *guard_loc, INIT_DIRECTION).len().into()
+    }
 
-type Coord = (usize, usize);
-type Direction = (isize, isize);
-
-fn parse_input(input: &str) -> (Vec<Vec<Slot>>, Coord) {
-    let mut grid = Vec::with_capacity(input.lines().count());
-    let mut guard_location = (0, 0);
-
-    for line in input.lines() {
-        let mut row = Vec::with_capacity(line.len());
-        for c in line.chars() {
-            let slot = match c {
-                '.' => Slot::Empty,
-                '#' => Slot::Obstacle,
-                '^' => {
-                    guard_location = (row.len(), grid.len());
-                    Slot::Empty
-                }
-                _ => panic!("unrecognized grid char: {}", c),
-            };
-
-            row.push(slot);
-        }
+    fn part2((map, guard_loc): &Self::Input<'_>) -> Answer {
+        let visited = trace_path(map, *guard_loc, INIT_DIRECTION);
 
-        grid.push(row);
+        make_loops(&mut map.clone(), *guard_loc, INIT_DIRECTION, &visited).into()
     }
+}
+
+const INIT_DIRECTION: Direction = Direction::Up;
 
-    (grid, guard_location)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Slot {
+    Empty,
+    Obstacle,
 }
 
-fn rotate_direction((a, b): Direction) -> Direction {
-    (-1 * b, a)
+fn parse_input(input: &str) -> Result<(Grid<Slot>, Coord), ParseError> {
+    let (grid, markers) = Grid::parse_with(input, "one of `.#^`", |c| match c {
+        '.' => Some(Tile::Cell(Slot::Empty)),
+        '#' => Some(Tile::Cell(Slot::Obstacle)),
+        '^' => Some(Tile::Marker(Slot::Empty)),
+        _ => None,
+    })?;
+    let guard_location = markers.require(input, '^', "a guard `^`")?;
+
+    Ok((grid, guard_location))
 }
 
-fn trace_path(
-    map: &Vec<Vec<Slot>>,
-    mut guard_loc: Coord,
-    mut direction: Direction,
-) -> HashSet<Coord> {
+fn trace_path(map: &Grid<Slot>, mut guard_loc: Coord, mut direction: Direction) -> HashSet<Coord> {
     let mut visited = HashSet::new();
 
     loop {
         visited.insert(guard_loc);
 
-        let (guard_x, guard_y) = guard_loc;
-        let (dir_x, dir_y) = direction;
-
-        let y_next = guard_y as isize + dir_y;
-        if y_next < 0 || y_next as usize >= map.len() {
-            break;
-        }
-        let y_next = y_next as usize;
-
-        let x_next = guard_x as isize + dir_x;
-        if x_next < 0 || x_next as usize >= map[y_next as usize].len() {
-            break;
-        }
-        let x_next = x_next as usize;
-
-        match map[y_next][x_next] {
-            Slot::Obstacle => {
-                direction = rotate_direction(direction);
+        let next = guard_loc.move_(direction);
+        match map.get(next) {
+            None => break,
+            Some(Slot::Obstacle) => {
+                direction = direction.turn_right();
             }
-            Slot::Empty => {
-                guard_loc = (x_next, y_next);
+            Some(Slot::Empty) => {
+                guard_loc = next;
             }
         };
     }
@@ -93,30 +69,30 @@ fn trace_path(
 }
 
 fn make_loops(
-    map: &mut Vec<Vec<Slot>>,
+    map: &mut Grid<Slot>,
     guard_loc: Coord,
     direction: Direction,
     visited_locations: &HashSet<Coord>,
 ) -> usize {
     let mut count = 0;
 
-    for &(x, y) in visited_locations {
-        if (x, y) == guard_loc {
+    for &location in visited_locations {
+        if location == guard_loc {
             continue;
         }
-        map[y][x] = Slot::Obstacle;
+        map[location] = Slot::Obstacle;
 
-        if check_loop(&map, guard_loc, direction) {
+        if check_loop(map, guard_loc, direction) {
             count += 1;
         }
 
-        map[y][x] = Slot::Empty;
+        map[location] = Slot::Empty;
     }
 
     count
 }
 
-fn check_loop(map: &Vec<Vec<Slot>>, mut guard_loc: Coord, mut direction: Direction) -> bool {
+fn check_loop(map: &Grid<Slot>, mut guard_loc: Coord, mut direction: Direction) -> bool {
     let mut visited = HashSet::new();
 
     loop {
@@ -126,28 +102,67 @@ fn check_loop(map: &Vec<Vec<Slot>>, mut guard_loc: Coord, mut direction: Directi
             visited.insert((guard_loc, direction));
         }
 
-        let (guard_x, guard_y) = guard_loc;
-        let (dir_x, dir_y) = direction;
-
-        let y_next = guard_y as isize + dir_y;
-        if y_next < 0 || y_next as usize >= map.len() {
-            return false;
-        }
-        let y_next = y_next as usize;
-
-        let x_next = guard_x as isize + dir_x;
-        if x_next < 0 || x_next as usize >= map[y_next as usize].len() {
-            return false;
-        }
-        let x_next = x_next as usize;
-
-        match map[y_next][x_next] {
-            Slot::Obstacle => {
-                direction = rotate_direction(direction);
+        let next = guard_loc.move_(direction);
+        match map.get(next) {
+            None => return false,
+            Some(Slot::Obstacle) => {
+                direction = direction.turn_right();
             }
-            Slot::Empty => {
-                guard_loc = (x_next, y_next);
+            Some(Slot::Empty) => {
+                guard_loc = next;
             }
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn finds_guard() {
+        let (map, guard_loc) = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(guard_loc, Coord(4, 6));
+        assert_eq!((map.width(), map.height()), (10, 10));
+    }
+
+    #[test]
+    fn detects_loops() {
+        let (mut map, guard_loc) = parse_input(EXAMPLE).unwrap();
+
+        assert!(!check_loop(&map, guard_loc, INIT_DIRECTION));
+
+        map[Coord(3, 6)] = Slot::Obstacle;
+        assert!(check_loop(&map, guard_loc, INIT_DIRECTION));
+    }
+
+    #[test]
+    fn rejects_unknown_cells() {
+        let error = parse_input("..#\n.^?\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.found, "`?`");
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day06::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day06::part1(&input), Answer::Number(41));
+        assert_eq!(Day06::part2(&input), Answer::Number(6));
+    }
+}
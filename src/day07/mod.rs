@@ -1,46 +1,55 @@
-const INPUT: &'static str = include_str!("./input.in");
-
-pub fn run() {
-    let equations = parse_input(INPUT);
-
-    let (succeeded, failed) = equations.iter().partition::<Vec<_>, _>(|eq| can_equate(eq));
-
-    let succeeded_tot = succeeded.iter().map(|it| it.target).sum::<usize>();
-    println!("Part 1: {}", succeeded_tot);
-    println!(
-        "Part 2: {}",
-        succeeded_tot
-            + failed
-                .iter()
-                .filter(|eq| can_equate_p2(eq))
-                .map(|it| it.target)
-                .sum::<usize>()
-    );
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: usize = 7;
+    type Input<'a> = Vec<Input>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(equations: &Self::Input<'_>) -> Answer {
+        equations
+            .iter()
+            .filter(|eq| can_equate(eq))
+            .map(|it| it.target)
+            .sum::<usize>()
+            .into()
+    }
+
+    fn part2(equations: &Self::Input<'_>) -> Answer {
+        equations
+            .iter()
+            .filter(|eq| can_equate(eq) || can_equate_p2(eq))
+            .map(|it| it.target)
+            .sum::<usize>()
+            .into()
+    }
 }
 
-struct Input {
+pub struct Input {
     target: usize,
     operands: Vec<usize>,
 }
 
-fn parse_input(input: &str) -> Vec<Input> {
+fn parse_input(input: &str) -> Result<Vec<Input>, ParseError> {
     input
         .lines()
         .map(|line| {
-            let Some((target, rest)) = line.split_once(": ") else {
-                panic!("Bad input: {}", line);
-            };
-            let target = target.parse::<usize>().unwrap();
+            let (target, rest) = parse::split_once(input, line, ": ")?;
+            let target = parse::number(input, target)?;
 
             let operands = rest
                 .split(" ")
-                .map(|it| it.parse::<usize>().unwrap())
-                .collect();
+                .map(|it| parse::number(input, it))
+                .collect::<Result<_, _>>()?;
 
-            Input {
-                target: target,
-                operands: operands,
-            }
+            Ok(Input { target, operands })
         })
         .collect()
 }
@@ -55,13 +64,13 @@ fn can_equate(input: &Input) -> bool {
         for &op in input.operands.iter().rev() {
             if pat % 2 == 0 && op <= remaining {
                 remaining -= op;
-            } else if pat % 2 == 1 && remaining % op == 0 {
-                remaining = remaining / op;
+            } else if pat % 2 == 1 && remaining.is_multiple_of(op) {
+                remaining /= op;
             } else {
                 continue 'outer;
             }
 
-            pat = pat / 2;
+            pat /= 2;
         }
         if remaining == 0 {
             return true;
@@ -84,15 +93,15 @@ fn can_equate_p2(input: &Input) -> bool {
                     continue 'outer;
                 };
                 remaining = res;
-            } else if pat % 3 == 1 && remaining % op == 0 {
-                remaining = remaining / op;
+            } else if pat % 3 == 1 && remaining.is_multiple_of(op) {
+                remaining /= op;
             } else if pat % 3 == 2 && op <= remaining {
                 remaining -= op;
             } else {
                 continue 'outer;
             }
 
-            pat = pat / 3;
+            pat /= 3;
         }
 
         if remaining == 0 {
@@ -121,3 +130,67 @@ fn remove_matching_end(num: usize, end: usize) -> Option<usize> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+    #[test]
+    fn parses_equations() {
+        let equations = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(equations.len(), 9);
+        assert_eq!(equations[1].target, 3267);
+        assert_eq!(equations[1].operands, vec![81, 40, 27]);
+    }
+
+    #[test]
+    fn equates_with_add_and_multiply() {
+        let equations = parse_input(EXAMPLE).unwrap();
+        let results = equations.iter().map(can_equate).collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            [true, true, false, false, false, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn equates_with_concatenation() {
+        let equations = parse_input(EXAMPLE).unwrap();
+        let results = equations.iter().map(can_equate_p2).collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            [true, true, false, true, true, false, true, false, true]
+        );
+    }
+
+    #[test]
+    fn removes_matching_end() {
+        assert_eq!(remove_matching_end(156, 6), Some(15));
+        assert_eq!(remove_matching_end(1510, 10), Some(15));
+        assert_eq!(remove_matching_end(156, 5), None);
+        assert_eq!(remove_matching_end(6, 6), Some(0));
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day07::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day07::part1(&input), Answer::Number(3749));
+        assert_eq!(Day07::part2(&input), Answer::Number(11387));
+    }
+}
@@ -1,44 +1,48 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::util::gcd;
+use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+    util::{gcd, Coord, Grid, Tile},
+};
 
-const INPUT: &'static str = include_str!("./input.in");
+pub struct Day08;
 
-pub fn run() {
-    let input = parse_input(&INPUT);
+impl Solution for Day08 {
+    const DAY: usize = 8;
+    type Input<'a> = AntennaMap;
 
-    println!("Part 1: {}", find_basic_antinodes(&input).len());
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
 
-    println!("Part 2: {}", find_harmonic_antinodes(&input).len());
-}
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        find_basic_antinodes(input).len().into()
+    }
 
-type Coord = (usize, usize);
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        find_harmonic_antinodes(input).len().into()
+    }
+}
 
-struct AntennaMap {
-    width: usize,
-    height: usize,
+pub struct AntennaMap {
+    grid: Grid<()>,
     antennae: HashMap<char, Vec<Coord>>,
 }
 
-fn parse_input(input: &str) -> AntennaMap {
-    let mut antennae = HashMap::<char, Vec<Coord>>::new();
-    let mut width = 0;
-    let mut height = 0;
-    for (y, row) in input.lines().enumerate() {
-        for (x, c) in row.chars().enumerate() {
-            if c != '.' {
-                antennae.entry(c).or_default().push((x, y));
-            }
-            width = x
-        }
-        height = y
-    }
+fn parse_input(input: &str) -> Result<AntennaMap, ParseError> {
+    let (grid, markers) = Grid::parse_with(input, "a letter, digit or `.`", |c| match c {
+        '.' => Some(Tile::Cell(())),
+        c if c.is_ascii_alphanumeric() => Some(Tile::Marker(())),
+        _ => None,
+    })?;
 
-    AntennaMap {
-        width: width + 1,
-        height: height + 1,
-        antennae: antennae,
-    }
+    let antennae = markers
+        .iter()
+        .map(|(frequency, coords)| (frequency, coords.to_vec()))
+        .collect();
+
+    Ok(AntennaMap { grid, antennae })
 }
 
 fn find_basic_antinodes(input: &AntennaMap) -> HashSet<Coord> {
@@ -47,17 +51,12 @@ fn find_basic_antinodes(input: &AntennaMap) -> HashSet<Coord> {
     for ants in input.antennae.values() {
         for i in 0..ants.len() {
             for j in i + 1..ants.len() {
-                let (x1, y1) = ants[i];
-                let (x2, y2) = ants[j];
+                let (a, b) = (ants[i], ants[j]);
 
-                let (ax1, ay1) = (2 * x1 as isize - x2 as isize, 2 * y1 as isize - y2 as isize);
-                let (ax2, ay2) = (2 * x2 as isize - x1 as isize, 2 * y2 as isize - y1 as isize);
-
-                if is_within(ax1, ay1, input.width as isize, input.height as isize) {
-                    antinodes.insert((ax1 as usize, ay1 as usize));
-                }
-                if is_within(ax2, ay2, input.width as isize, input.height as isize) {
-                    antinodes.insert((ax2 as usize, ay2 as usize));
+                for antinode in [a * 2 - b, b * 2 - a] {
+                    if input.grid.contains(antinode) {
+                        antinodes.insert(antinode);
+                    }
                 }
             }
         }
@@ -76,24 +75,14 @@ fn find_harmonic_antinodes(input: &AntennaMap) -> HashSet<Coord> {
                     continue;
                 }
 
-                let (x1, y1) = ants[i];
-                let (x2, y2) = ants[j];
-
-                let (dx, dy) = {
-                    let dx = x1 as isize - x2 as isize;
-                    let dy = y1 as isize - y2 as isize;
-
-                    let factor = gcd(dx.abs(), dy.abs());
-
-                    (dx / factor, dy / factor)
-                };
+                let Coord(dx, dy) = ants[i] - ants[j];
+                let factor = gcd(dx.abs(), dy.abs());
 
-                let (mut curr_x, mut curr_y) = (x1 as isize, y1 as isize);
-                while is_within(curr_x, curr_y, input.width as isize, input.height as isize) {
-                    antinodes.insert((curr_x as usize, curr_y as usize));
-                    curr_x += dx;
-                    curr_y += dy;
-                }
+                antinodes.extend(
+                    ants[i]
+                        .ray(Coord(dx / factor, dy / factor))
+                        .within(&input.grid),
+                );
             }
         }
     }
@@ -101,6 +90,42 @@ fn find_harmonic_antinodes(input: &AntennaMap) -> HashSet<Coord> {
     antinodes
 }
 
-fn is_within(x: isize, y: isize, width: isize, height: isize) -> bool {
-    0 <= x && x < width && 0 <= y && y < height
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+
+    #[test]
+    fn parses_antennae() {
+        let map = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!((map.grid.width(), map.grid.height()), (12, 12));
+        assert_eq!(map.antennae[&'0'].len(), 4);
+        assert_eq!(
+            map.antennae[&'A'],
+            vec![Coord(6, 5), Coord(8, 8), Coord(9, 9)]
+        );
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day08::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day08::part1(&input), Answer::Number(14));
+        assert_eq!(Day08::part2(&input), Answer::Number(34));
+    }
 }
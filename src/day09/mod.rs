@@ -1,16 +1,33 @@
 use std::iter;
 
-const INPUT: &'static str = include_str!("./input.in");
+use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
 
-pub fn run() {
-    let disk = parse_input(INPUT);
-    let mut raw_disk = to_raw_disk(&disk);
-    compact_raw_disk(&mut raw_disk);
-    println!("Part 1: {}", check_sum_raw(&raw_disk));
+pub struct Day09;
 
-    let mut raw_disk_p2 = to_raw_disk(&disk);
-    compact_whole_files(&mut raw_disk_p2);
-    println!("Part 2: {}", check_sum_raw(&raw_disk_p2));
+impl Solution for Day09 {
+    const DAY: usize = 9;
+    type Input<'a> = Vec<FileBlock>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(disk: &Self::Input<'_>) -> Answer {
+        let mut raw_disk = to_raw_disk(disk);
+        compact_raw_disk(&mut raw_disk);
+
+        check_sum_raw(&raw_disk).into()
+    }
+
+    fn part2(disk: &Self::Input<'_>) -> Answer {
+        let mut raw_disk = to_raw_disk(disk);
+        compact_whole_files(&mut raw_disk);
+
+        check_sum_raw(&raw_disk).into()
+    }
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
@@ -20,23 +37,30 @@ enum FileType {
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
-struct FileBlock {
+pub struct FileBlock {
     count: usize,
     file_type: FileType,
 }
 
-fn to_raw_disk(input: &Vec<FileBlock>) -> Vec<FileType> {
+fn to_raw_disk(input: &[FileBlock]) -> Vec<FileType> {
     input
         .iter()
-        .map(|it| iter::repeat_n(it.file_type, it.count))
-        .flatten()
+        .flat_map(|it| iter::repeat_n(it.file_type, it.count))
         .collect()
 }
 
-fn parse_input(input: &str) -> Vec<FileBlock> {
-    input
-        .chars()
-        .map(|c| c.to_digit(10).unwrap())
+fn parse_input(input: &str) -> Result<Vec<FileBlock>, ParseError> {
+    let digits = input
+        .trim_end()
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .ok_or_else(|| ParseError::at(input, &input[i..i + c.len_utf8()], "a digit"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(digits
+        .into_iter()
         .enumerate()
         .filter(|&(_, c)| c > 0)
         .map(|(i, c)| {
@@ -51,10 +75,10 @@ fn parse_input(input: &str) -> Vec<FileBlock> {
                 file_type,
             }
         })
-        .collect()
+        .collect())
 }
 
-fn compact_raw_disk(disk: &mut Vec<FileType>) {
+fn compact_raw_disk(disk: &mut [FileType]) {
     let mut last_occupied = disk.len() - 1;
     for i in 0..disk.len() {
         while disk[last_occupied] == FileType::Free {
@@ -66,14 +90,12 @@ fn compact_raw_disk(disk: &mut Vec<FileType>) {
         }
 
         if disk[i] == FileType::Free {
-            let tmp = disk[i];
-            disk[i] = disk[last_occupied];
-            disk[last_occupied] = tmp;
+            disk.swap(i, last_occupied);
         }
     }
 }
 
-fn check_sum_raw(disk: &Vec<FileType>) -> usize {
+fn check_sum_raw(disk: &[FileType]) -> usize {
     disk.iter()
         .enumerate()
         .map(|(i, typ)| match typ {
@@ -83,11 +105,11 @@ fn check_sum_raw(disk: &Vec<FileType>) -> usize {
         .sum::<usize>()
 }
 
-fn compact_whole_files(disk: &mut Vec<FileType>) {
+fn compact_whole_files(disk: &mut [FileType]) {
     let mut ix = disk.len() - 1;
     loop {
         let chunk_end = ix;
-        let chunk_start = find_chunk_start(&disk, chunk_end);
+        let chunk_start = find_chunk_start(disk, chunk_end);
 
         if disk[chunk_end] != FileType::Free {
             let mut trg_start = 0;
@@ -113,7 +135,7 @@ fn compact_whole_files(disk: &mut Vec<FileType>) {
     }
 }
 
-fn swap_chunk(arr: &mut Vec<FileType>, src_start: usize, target_start: usize, len: usize) {
+fn swap_chunk(arr: &mut [FileType], src_start: usize, target_start: usize, len: usize) {
     for ix in 0..len {
         let tmp = arr[target_start + ix];
         arr[target_start + ix] = arr[src_start + ix];
@@ -121,7 +143,7 @@ fn swap_chunk(arr: &mut Vec<FileType>, src_start: usize, target_start: usize, le
     }
 }
 
-fn find_chunk_start(disk: &Vec<FileType>, end: usize) -> usize {
+fn find_chunk_start(disk: &[FileType], end: usize) -> usize {
     let mut start = end;
     while start >= 1 && disk[start - 1] == disk[end] {
         start -= 1;
@@ -130,7 +152,7 @@ fn find_chunk_start(disk: &Vec<FileType>, end: usize) -> usize {
     start
 }
 
-fn find_chunk_end(disk: &Vec<FileType>, start: usize) -> usize {
+fn find_chunk_end(disk: &[FileType], start: usize) -> usize {
     let mut end = start;
     while end < disk.len() - 1 && disk[end + 1] == disk[start] {
         end += 1;
@@ -138,3 +160,50 @@ fn find_chunk_end(disk: &Vec<FileType>, start: usize) -> usize {
 
     end
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2333133121414131402";
+
+    fn render(disk: &[FileType]) -> String {
+        disk.iter()
+            .map(|it| match it {
+                FileType::Free => '.',
+                FileType::Occupied(id) => char::from_digit(*id as u32, 10).unwrap(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn expands_disk_map() {
+        let disk = to_raw_disk(&parse_input("12345").unwrap());
+
+        assert_eq!(render(&disk), "0..111....22222");
+    }
+
+    #[test]
+    fn compacts_blocks() {
+        let mut disk = to_raw_disk(&parse_input("12345").unwrap());
+        compact_raw_disk(&mut disk);
+
+        assert_eq!(render(&disk), "022111222......");
+    }
+
+    #[test]
+    fn compacts_whole_files() {
+        let mut disk = to_raw_disk(&parse_input(EXAMPLE).unwrap());
+        compact_whole_files(&mut disk);
+
+        assert_eq!(render(&disk), "00992111777.44.333....5555.6666.....8888..");
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day09::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day09::part1(&input), Answer::Number(1928));
+        assert_eq!(Day09::part2(&input), Answer::Number(2858));
+    }
+}
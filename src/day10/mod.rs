@@ -1,129 +1,138 @@
 use std::collections::HashSet;
 
-const INPUT: &'static str = include_str!("./input.in");
+use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+    util::{Coord, Grid},
+};
 
-pub fn run() {
-    let map = parse_input(INPUT);
-    let trail_heads = find_trail_heads(&map);
-    let total_score = trail_heads
-        .iter()
-        .map(|th| trail_head_score(&map, *th))
-        .sum::<usize>();
+pub struct Day10;
 
-    println!("Part 1: {}", total_score);
+impl Solution for Day10 {
+    const DAY: usize = 10;
+    type Input<'a> = Grid<usize>;
 
-    let total_rating = trail_heads
-        .iter()
-        .map(|th| trail_head_rating(&map, *th))
-        .sum::<usize>();
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
 
-    println!("Part 1: {}", total_rating);
-}
+    fn part1(map: &Self::Input<'_>) -> Answer {
+        find_trail_heads(map)
+            .iter()
+            .map(|th| trail_head_score(map, *th))
+            .sum::<usize>()
+            .into()
+    }
 
-fn parse_input(input: &str) -> Vec<Vec<usize>> {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as usize)
-                .collect()
-        })
-        .collect()
+    fn part2(map: &Self::Input<'_>) -> Answer {
+        find_trail_heads(map)
+            .iter()
+            .map(|th| trail_head_rating(map, *th))
+            .sum::<usize>()
+            .into()
+    }
 }
 
-type Coord = (usize, usize);
+fn parse_input(input: &str) -> Result<Grid<usize>, ParseError> {
+    Grid::parse(input, "a digit", |c, _| c.to_digit(10).map(|d| d as usize))
+}
 
-fn find_trail_heads(map: &Vec<Vec<usize>>) -> Vec<Coord> {
+fn find_trail_heads(map: &Grid<usize>) -> Vec<Coord> {
     map.iter()
-        .enumerate()
-        .map(|(y, row)| {
-            row.iter().enumerate().filter_map(
-                move |(x, &height)| {
-                    if height == 0 {
-                        Some((x, y))
-                    } else {
-                        None
-                    }
-                },
-            )
-        })
-        .flatten()
+        .filter(|&(_, &height)| height == 0)
+        .map(|(coord, _)| coord)
         .collect()
 }
 
-fn trail_head_score(map: &Vec<Vec<usize>>, start: Coord) -> usize {
+fn trail_head_score(map: &Grid<usize>, start: Coord) -> usize {
     let mut score = 0;
     let mut stack = vec![start];
     let mut visited = HashSet::new();
 
-    while let Some((x, y)) = stack.pop() {
-        if visited.contains(&(x, y)) {
+    while let Some(coord) = stack.pop() {
+        if visited.contains(&coord) {
             continue;
         }
 
-        visited.insert((x, y));
+        visited.insert(coord);
 
-        let curr = map[y][x];
+        let curr = map[coord];
         if curr == 9 {
             score += 1;
             continue;
         }
 
-        let nexts = [
-            (x as isize, y as isize - 1),
-            (x as isize - 1, y as isize),
-            (x as isize, y as isize + 1),
-            (x as isize + 1, y as isize),
-        ];
-
-        for next in nexts {
-            if let Some(v) = get(map, next) {
-                if v == curr + 1 {
-                    stack.push((next.0 as usize, next.1 as usize))
-                }
+        for next in coord.adjacent() {
+            if map.get(next) == Some(&(curr + 1)) {
+                stack.push(next)
             }
         }
     }
     score
 }
 
-fn get<T: Copy>(src: &Vec<Vec<T>>, (x, y): (isize, isize)) -> Option<T> {
-    if x < 0 || y < 0 {
-        return None;
-    }
-
-    let (x, y) = (x as usize, y as usize);
-
-    if y < src.len() && x < src[y].len() {
-        Some(src[y][x])
-    } else {
-        None
-    }
-}
-
-fn trail_head_rating(map: &Vec<Vec<usize>>, start: Coord) -> usize {
+fn trail_head_rating(map: &Grid<usize>, start: Coord) -> usize {
     let mut score = 0;
     let mut stack = vec![start];
 
-    while let Some((x, y)) = stack.pop() {
-        let curr = map[y][x];
+    while let Some(coord) = stack.pop() {
+        let curr = map[coord];
         if curr == 9 {
             score += 1;
             continue;
         }
 
-        if y > 0 && map[y - 1][x] == curr + 1 {
-            stack.push((x, y - 1))
-        }
-        if x > 0 && map[y][x - 1] == curr + 1 {
-            stack.push((x - 1, y))
-        }
-        if y + 1 < map.len() && map[y + 1][x] == curr + 1 {
-            stack.push((x, y + 1));
-        }
-        if x + 1 < map[y].len() && map[y][x + 1] == curr + 1 {
-            stack.push((x + 1, y))
+        for next in coord.adjacent() {
+            if map.get(next) == Some(&(curr + 1)) {
+                stack.push(next)
+            }
         }
     }
     score
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
+
+    #[test]
+    fn scores_trail_heads() {
+        let map = parse_input(EXAMPLE).unwrap();
+        let scores = find_trail_heads(&map)
+            .iter()
+            .map(|th| trail_head_score(&map, *th))
+            .collect::<Vec<_>>();
+
+        assert_eq!(scores, [5, 6, 5, 3, 1, 3, 5, 3, 5]);
+    }
+
+    #[test]
+    fn rates_trail_heads() {
+        let map = parse_input(EXAMPLE).unwrap();
+        let ratings = find_trail_heads(&map)
+            .iter()
+            .map(|th| trail_head_rating(&map, *th))
+            .collect::<Vec<_>>();
+
+        assert_eq!(ratings, [20, 24, 10, 4, 1, 4, 5, 8, 5]);
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day10::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day10::part1(&input), Answer::Number(36));
+        assert_eq!(Day10::part2(&input), Answer::Number(81));
+    }
+}
@@ -1,25 +1,33 @@
-use crate::util::Counter;
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+    util::Counter,
+};
 
-const INPUT: &'static str = include_str!("./input.in");
+pub struct Day11;
 
-pub fn run() {
-    let mut stones_count = parse_input(INPUT);
+impl Solution for Day11 {
+    const DAY: usize = 11;
+    type Input<'a> = Counter<usize>;
 
-    for _ in 0..25 {
-        stones_count = blink(stones_count);
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
     }
 
-    println!("Part 1: {}", stones_count.total(),);
-
-    for _ in 0..50 {
-        stones_count = blink(stones_count);
+    fn part1(stones_count: &Self::Input<'_>) -> Answer {
+        blink_times(stones_count.clone(), 25).total().into()
     }
 
-    println!("Part 2: {}", stones_count.total(),);
+    fn part2(stones_count: &Self::Input<'_>) -> Answer {
+        blink_times(stones_count.clone(), 75).total().into()
+    }
 }
 
-pub fn parse_input(input: &str) -> Counter<usize> {
-    input.split(" ").map(|it| it.parse().unwrap()).collect()
+pub fn parse_input(input: &str) -> Result<Counter<usize>, ParseError> {
+    input
+        .split_ascii_whitespace()
+        .map(|it| parse::number(input, it))
+        .collect()
 }
 
 fn number_len(stone: usize) -> u32 {
@@ -32,7 +40,7 @@ fn blink_one(stone: usize) -> (usize, Option<usize>) {
     }
 
     let len = number_len(stone);
-    if len % 2 == 0 {
+    if len.is_multiple_of(2) {
         return (
             stone / 10usize.pow(len / 2),
             Some(stone % 10usize.pow(len / 2)),
@@ -45,8 +53,8 @@ fn blink_one(stone: usize) -> (usize, Option<usize>) {
 fn blink(counts: Counter<usize>) -> Counter<usize> {
     let mut new_counts = Counter::new();
 
-    for (stone, count) in counts.iter() {
-        let (a, maybe_b) = blink_one(*stone);
+    for (stone, count) in counts {
+        let (a, maybe_b) = blink_one(stone);
         new_counts.count_n(a, count);
         if let Some(b) = maybe_b {
             new_counts.count_n(b, count);
@@ -55,3 +63,40 @@ fn blink(counts: Counter<usize>) -> Counter<usize> {
 
     new_counts
 }
+
+fn blink_times(mut counts: Counter<usize>, times: usize) -> Counter<usize> {
+    for _ in 0..times {
+        counts = blink(counts);
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blinks_one_stone() {
+        assert_eq!(blink_one(0), (1, None));
+        assert_eq!(blink_one(1), (2024, None));
+        assert_eq!(blink_one(1000), (10, Some(0)));
+        assert_eq!(blink_one(99), (9, Some(9)));
+    }
+
+    #[test]
+    fn blinks_example() {
+        let stones = parse_input("125 17").unwrap();
+
+        assert_eq!(blink_times(stones.clone(), 6).total(), 22);
+        assert_eq!(blink_times(stones, 25).total(), 55312);
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day11::parse("125 17").unwrap();
+
+        assert_eq!(Day11::part1(&input), Answer::Number(55312));
+        assert_eq!(Day11::part2(&input), Answer::Number(65601038650482));
+    }
+}
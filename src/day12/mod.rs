@@ -1,148 +1,116 @@
-use std::collections::HashSet;
+use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+    util::Grid,
+};
 
-use crate::util::{parse_char_grid, Coord, Direction, Grid};
+pub struct Day12;
 
-const INPUT: &'static str = include_str!("./input.in");
+impl Solution for Day12 {
+    const DAY: usize = 12;
+    type Input<'a> = Grid<char>;
 
-pub fn run() {
-    let grid = parse_char_grid(INPUT);
-    let regions = find_contiguous_regions(&grid);
-    let cost_p1 = regions
-        .iter()
-        .map(|r| r.area.len() * count_fence_pieces(r))
-        .sum::<usize>();
-
-    println!("Part 1: {}", cost_p1);
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Grid::parse(input, "a capital letter", |c, _| {
+            c.is_ascii_uppercase().then_some(c)
+        })
+    }
 
-    let cost_p2 = regions
-        .iter()
-        .map(|r| r.area.len() * count_fence_lines(r))
-        .sum::<usize>();
+    fn part1(grid: &Self::Input<'_>) -> Answer {
+        grid.components(|a, b| a == b)
+            .regions()
+            .map(|region| region.area * region.perimeter)
+            .sum::<usize>()
+            .into()
+    }
 
-    println!("Part 2: {}", cost_p2);
+    fn part2(grid: &Self::Input<'_>) -> Answer {
+        grid.components(|a, b| a == b)
+            .regions()
+            .map(|region| region.area * region.side_count())
+            .sum::<usize>()
+            .into()
+    }
 }
 
-struct Region {
-    area: HashSet<Coord>,
-    edges: HashSet<Coord>,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{parse_char_grid, region::Components, Coord};
+
+    const SMALL_EXAMPLE: &str = "\
+AAAA
+BBCD
+BBCC
+EEEC
+";
+
+    const LARGE_EXAMPLE: &str = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+
+    fn regions(grid: &str) -> Components {
+        parse_char_grid(grid).components(|a, b| a == b)
+    }
 
-fn find_contiguous_regions(grid: &Grid<char>) -> Vec<Region> {
-    let mut visited = HashSet::new();
-    let mut regions = vec![];
-    for coord in grid.iter_coords() {
-        if visited.contains(&coord) {
-            continue;
-        }
-
-        visited.insert(coord);
-        let Some(region) = find_contiguous_region(grid, coord) else {
-            continue;
-        };
-
-        visited.extend(&region.area);
-        regions.push(region)
+    fn sides_at(components: &Components, coord: Coord) -> usize {
+        components.region_at(coord).unwrap().side_count()
     }
 
-    regions
-}
+    #[test]
+    fn measures_regions() {
+        let components = regions(SMALL_EXAMPLE);
+
+        assert_eq!(components.len(), 5);
 
-fn find_contiguous_region(grid: &Grid<char>, start: Coord) -> Option<Region> {
-    let Some(&region_type) = grid.get(start) else {
-        return None;
-    };
-
-    let mut area = HashSet::new();
-    let mut stack = vec![start];
-    while let Some(coord) = stack.pop() {
-        area.insert(coord);
-
-        for dir in Direction::all_directions() {
-            let next = coord.move_(dir);
-            if area.contains(&next) {
-                continue;
-            }
-
-            match grid.get(next) {
-                Some(&c) if c == region_type => stack.push(next),
-                _ => {}
-            }
-        }
+        let a = components.region_at(Coord(0, 0)).unwrap();
+        assert_eq!((a.area, a.perimeter), (4, 10));
+
+        let c = components.region_at(Coord(2, 1)).unwrap();
+        assert_eq!((c.area, c.perimeter), (4, 10));
     }
 
-    let edges = find_edges(&area);
+    #[test]
+    fn counts_fence_lines() {
+        let components = regions(SMALL_EXAMPLE);
 
-    Some(Region { area, edges })
-}
+        assert_eq!(sides_at(&components, Coord(0, 0)), 4);
+        assert_eq!(sides_at(&components, Coord(0, 1)), 4);
+        assert_eq!(sides_at(&components, Coord(2, 1)), 8);
+        assert_eq!(sides_at(&components, Coord(3, 1)), 4);
+        assert_eq!(sides_at(&components, Coord(0, 3)), 4);
+    }
 
-fn find_edges(region_area: &HashSet<Coord>) -> HashSet<Coord> {
-    region_area
-        .iter()
-        .filter(|square| {
-            Direction::all_directions()
-                .into_iter()
-                .any(|dir| !region_area.contains(&square.move_(dir)))
-        })
-        .map(|c| *c)
-        .collect()
-}
+    #[test]
+    fn counts_fence_lines_around_holes() {
+        let components = regions("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE\n");
 
-fn count_fence_pieces(region: &Region) -> usize {
-    region
-        .edges
-        .iter()
-        .map(|edge| {
-            Direction::all_directions()
-                .iter()
-                .filter(|&&direction| !region.area.contains(&edge.move_(direction)))
-                .count()
-        })
-        .sum()
-}
+        assert_eq!(sides_at(&components, Coord(0, 0)), 12);
 
-fn count_fence_lines(region: &Region) -> usize {
-    region
-        .edges
-        .iter()
-        .flat_map(|edge| get_lines_including(*edge, &region))
-        .collect::<HashSet<_>>()
-        .len()
-}
+        let components = regions("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n");
 
-#[derive(Hash, PartialEq, Eq, Clone, Copy, Debug)]
-struct Line {
-    direction: Direction,
-    start: Coord,
-    end: Coord,
-}
+        assert_eq!(sides_at(&components, Coord(0, 0)), 12);
+    }
 
-fn get_lines_including(edge: Coord, region: &Region) -> Vec<Line> {
-    Direction::all_directions()
-        .iter()
-        .filter_map(|&direction| {
-            // Is this an edge for the given direction?
-            // i.e. if we move in the given direction, do we exit the region?
-            if region.area.contains(&edge.move_(direction)) {
-                return None;
-            }
-
-            let [to_start, to_end] = direction.perpendicular_directions();
-
-            let start = edge.move_while(to_start, |coord| {
-                // If we move `to_start`, are we still on an edge for `direction`?
-                region.edges.contains(&coord) && !region.area.contains(&coord.move_(direction))
-            });
-
-            let end = edge.move_while(to_end, |coord| {
-                // If we move `to_end`, are we still on an edge for `direction`?
-                region.edges.contains(&coord) && !region.area.contains(&coord.move_(direction))
-            });
-
-            Some(Line {
-                direction,
-                start,
-                end,
-            })
-        })
-        .collect()
+    #[test]
+    fn solves_examples() {
+        let input = Day12::parse(SMALL_EXAMPLE).unwrap();
+
+        assert_eq!(Day12::part1(&input), Answer::Number(140));
+        assert_eq!(Day12::part2(&input), Answer::Number(80));
+
+        let input = Day12::parse(LARGE_EXAMPLE).unwrap();
+
+        assert_eq!(Day12::part1(&input), Answer::Number(1930));
+        assert_eq!(Day12::part2(&input), Answer::Number(1206));
+    }
 }
@@ -1,46 +1,57 @@
 use regex::Regex;
 use std::{str::FromStr, sync::LazyLock};
 
-use crate::util::Ratio;
-
-const INPUT: &'static str = include_str!("./input.in");
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+    util::Ratio,
+};
 
 static BUTTON_LINE_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r".*X\+(\d+).*Y\+(\d+)").unwrap());
 static PRIZE_LINE_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r".*X=(\d+).*Y=(\d+)").unwrap());
 
-const A_COST: isize = 3;
-const B_COST: isize = 1;
+const A_COST: i128 = 3;
+const B_COST: i128 = 1;
 
-pub fn run() {
-    let claw_machines = parse_input(INPUT);
+pub struct Day13;
 
-    let cost = claw_machines
-        .iter()
-        .filter_map(|cm| find_prize(cm))
-        .sum::<isize>();
+impl Solution for Day13 {
+    const DAY: usize = 13;
+    type Input<'a> = Vec<ClawMachine>;
 
-    println!("Part 1: {}", cost);
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
 
-    let cost = claw_machines
-        .iter()
-        .filter_map(|cm| {
-            find_prize(&ClawMachine {
-                prize: (cm.prize.0 + 10000000000000, cm.prize.1 + 10000000000000),
-                ..*cm
-            })
-        })
-        .sum::<isize>();
+    fn part1(claw_machines: &Self::Input<'_>) -> Answer {
+        claw_machines
+            .iter()
+            .filter_map(find_prize)
+            .sum::<i128>()
+            .into()
+    }
 
-    println!("Part 2: {}", cost);
+    fn part2(claw_machines: &Self::Input<'_>) -> Answer {
+        claw_machines
+            .iter()
+            .filter_map(|cm| {
+                find_prize(&ClawMachine {
+                    prize: (cm.prize.0 + 10000000000000, cm.prize.1 + 10000000000000),
+                    ..*cm
+                })
+            })
+            .sum::<i128>()
+            .into()
+    }
 }
 
-fn parse_input(input: &str) -> Vec<ClawMachine> {
+fn parse_input(input: &str) -> Result<Vec<ClawMachine>, ParseError> {
     input
         .split("\n\n")
-        .filter(|it| it.trim().len() > 0)
-        .map(|cm| cm.parse().unwrap())
+        .filter(|it| !it.trim().is_empty())
+        .map(|cm| parse_claw_machine(input, cm))
         .collect()
 }
 
@@ -50,50 +61,138 @@ fn find_prize(
         b: (bx, by),
         prize: (px, py),
     }: &ClawMachine,
-) -> Option<isize> {
+) -> Option<i128> {
+    let [ax, ay, bx, by, px, py] = [ax, ay, bx, by, px, py].map(|n| n as i128);
     let det = ax * by - bx * ay;
 
-    let a = Ratio::new(px * by - bx * py, det);
-    let b = Ratio::new(ax * py - px * ay, det);
+    // With the buttons in line there is no single solution to find.
+    let a = Ratio::checked_new(px * by - bx * py, det)?.to_integer()?;
+    let b = Ratio::checked_new(ax * py - px * ay, det)?.to_integer()?;
 
-    if a.is_integer() && b.is_integer() {
-        Some(A_COST * a.as_integer() + B_COST * b.as_integer())
-    } else {
-        None
-    }
+    Some(A_COST * a + B_COST * b)
 }
 
 #[derive(Debug)]
-struct ClawMachine {
+pub struct ClawMachine {
     prize: (isize, isize),
     a: (isize, isize),
     b: (isize, isize),
 }
 
 impl FromStr for ClawMachine {
-    type Err = ();
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-
-        let a = parse_button_line(lines.next().unwrap());
-        let b = parse_button_line(lines.next().unwrap());
-        let prize = parse_prize_line(lines.next().unwrap());
-        Ok(ClawMachine { a, b, prize })
+        parse_claw_machine(s, s)
     }
 }
 
-fn parse_button_line(line: &str) -> (isize, isize) {
-    let caps = BUTTON_LINE_RE.captures(line).unwrap();
+/// Parses the claw machine described by `s`, a slice of `input`.
+fn parse_claw_machine(input: &str, s: &str) -> Result<ClawMachine, ParseError> {
+    let mut lines = s.lines();
+
+    let a = parse_line(
+        input,
+        parse::next(input, s, &mut lines, "a button line")?,
+        &BUTTON_LINE_RE,
+        "`Button A: X+<n>, Y+<n>`",
+    )?;
+    let b = parse_line(
+        input,
+        parse::next(input, s, &mut lines, "a button line")?,
+        &BUTTON_LINE_RE,
+        "`Button B: X+<n>, Y+<n>`",
+    )?;
+    let prize = parse_line(
+        input,
+        parse::next(input, s, &mut lines, "a prize line")?,
+        &PRIZE_LINE_RE,
+        "`Prize: X=<n>, Y=<n>`",
+    )?;
+    Ok(ClawMachine { a, b, prize })
+}
+
+fn parse_line(
+    input: &str,
+    line: &str,
+    re: &Regex,
+    expected: &str,
+) -> Result<(isize, isize), ParseError> {
+    let caps = re
+        .captures(line)
+        .ok_or_else(|| ParseError::at(input, line, expected))?;
 
     let (_, [x, y]) = caps.extract();
 
-    (x.parse().unwrap(), y.parse().unwrap())
+    Ok((parse::number(input, x)?, parse::number(input, y)?))
 }
 
-fn parse_prize_line(line: &str) -> (isize, isize) {
-    let caps = PRIZE_LINE_RE.captures(line).unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279
+";
+
+    #[test]
+    fn parses_claw_machine() {
+        let claw_machine: ClawMachine = "\
+Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400"
+            .parse()
+            .unwrap();
+
+        assert_eq!(claw_machine.a, (94, 34));
+        assert_eq!(claw_machine.b, (22, 67));
+        assert_eq!(claw_machine.prize, (8400, 5400));
+    }
 
-    let (_, [x, y]) = caps.extract();
+    #[test]
+    fn finds_prizes() {
+        let prizes = parse_input(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(find_prize)
+            .collect::<Vec<_>>();
+
+        assert_eq!(prizes, [Some(280), None, Some(200), None]);
+    }
+
+    #[test]
+    fn rejects_malformed_claw_machines() {
+        let error = "Button A: X+94, Y+34\nButton B: X+22\nPrize: X=8400, Y=5400"
+            .parse::<ClawMachine>()
+            .unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "`Button B: X+<n>, Y+<n>`");
 
-    (x.parse().unwrap(), y.parse().unwrap())
+        let error = parse_input("Button A: X+94, Y+34\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.found, "end of input");
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day13::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day13::part1(&input), Answer::Number(480));
+        assert_eq!(Day13::part2(&input), Answer::Number(875318608908));
+    }
 }
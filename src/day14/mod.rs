@@ -2,52 +2,107 @@ use std::{collections::HashMap, sync::LazyLock};
 
 use regex::Regex;
 
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+    util::{Coord, Grid},
+};
+
 static ROBOT_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap());
 
-const INPUT: &'static str = include_str!("./input.in");
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: usize = 14;
+    type Input<'a> = Vec<Robot>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input, WIDTH, HEIGHT)
+    }
 
-pub fn run() {
-    let robots = parse_input(INPUT);
-    let mut map = init_map(&robots);
-    for _ in 0..100 {
-        map = step(map)
+    fn part1(robots: &Self::Input<'_>) -> Answer {
+        safety_factor(robots, WIDTH, HEIGHT, 100).into()
     }
 
-    println!("Part 1: {}", count_quadrants(&map));
+    fn part2(robots: &Self::Input<'_>) -> Answer {
+        let mut map = init_map(robots);
+        let mut i = 0usize;
+        while !is_maybe_christmas_tree(&map, WIDTH, HEIGHT) {
+            i += 1;
+            map = step(map, WIDTH, HEIGHT);
+        }
+
+        i.into()
+    }
+}
+
+const WIDTH: isize = 101;
+const HEIGHT: isize = 103;
 
-    let mut i = 100;
-    while !is_maybe_christmas_tree(&map) {
-        i += 1;
-        map = step(map);
+fn safety_factor(robots: &[Robot], width: isize, height: isize, seconds: usize) -> usize {
+    let mut map = init_map(robots);
+    for _ in 0..seconds {
+        map = step(map, width, height)
     }
-    println!("Part 2: {}", i);
+
+    count_quadrants(&map, width, height)
 }
 
-#[derive(Clone, Copy)]
-struct Robot {
+#[derive(Debug, Clone, Copy)]
+pub struct Robot {
     position: (usize, usize),
     velocity: (isize, isize),
 }
 
-fn parse_input(input: &str) -> Vec<Robot> {
+/// Parses robots for a `width` by `height` grid. `step` wraps a robot back on
+/// to the grid at most once per move, so each has to start on it and move
+/// less than its size each second.
+fn parse_input(input: &str, width: isize, height: isize) -> Result<Vec<Robot>, ParseError> {
+    let position = |token: &str, size: isize| {
+        let value = parse::number(input, token)?;
+        if value < size as usize {
+            Ok(value)
+        } else {
+            Err(ParseError::at(
+                input,
+                token,
+                format!("a position below {}", size),
+            ))
+        }
+    };
+    let velocity = |token: &str, size: isize| {
+        let value: isize = parse::number(input, token)?;
+        if value.abs() < size {
+            Ok(value)
+        } else {
+            Err(ParseError::at(
+                input,
+                token,
+                format!("a velocity between -{} and {}, exclusive", size, size),
+            ))
+        }
+    };
+
     input
         .lines()
         .map(|line| {
-            let parsed = ROBOT_RE.captures(line).unwrap();
+            let parsed = ROBOT_RE
+                .captures(line)
+                .ok_or_else(|| ParseError::at(input, line, "`p=<x>,<y> v=<dx>,<dy>`"))?;
             let (_, [px, py, vx, vy]) = parsed.extract();
 
-            Robot {
-                position: (px.parse().unwrap(), py.parse().unwrap()),
-                velocity: (vx.parse().unwrap(), vy.parse().unwrap()),
-            }
+            Ok(Robot {
+                position: (position(px, width)?, position(py, height)?),
+                velocity: (velocity(vx, width)?, velocity(vy, height)?),
+            })
         })
         .collect()
 }
 
 type TileMap = HashMap<(usize, usize), Vec<Robot>>;
 
-fn init_map(robots: &Vec<Robot>) -> TileMap {
+fn init_map(robots: &[Robot]) -> TileMap {
     let mut map = HashMap::<(usize, usize), Vec<Robot>>::new();
 
     for robot in robots {
@@ -57,10 +112,7 @@ fn init_map(robots: &Vec<Robot>) -> TileMap {
     map
 }
 
-const WIDTH: isize = 101;
-const HEIGHT: isize = 103;
-
-fn step(map: TileMap) -> TileMap {
+fn step(map: TileMap, width: isize, height: isize) -> TileMap {
     let mut new_map: TileMap = HashMap::new();
     for (pos, robots) in map {
         let (px, py) = pos;
@@ -71,14 +123,14 @@ fn step(map: TileMap) -> TileMap {
             let mut py = py as isize + vy;
 
             if px < 0 {
-                px += WIDTH;
-            } else if px >= WIDTH {
-                px -= WIDTH;
+                px += width;
+            } else if px >= width {
+                px -= width;
             }
             if py < 0 {
-                py += HEIGHT
-            } else if py >= HEIGHT {
-                py -= HEIGHT
+                py += height
+            } else if py >= height {
+                py -= height
             }
 
             new_map
@@ -91,9 +143,9 @@ fn step(map: TileMap) -> TileMap {
     new_map
 }
 
-fn determine_quadrant(x: usize, y: usize) -> Option<usize> {
-    let midwidth = (WIDTH as usize) / 2;
-    let midheight = (HEIGHT as usize) / 2;
+fn determine_quadrant(x: usize, y: usize, width: isize, height: isize) -> Option<usize> {
+    let midwidth = (width as usize) / 2;
+    let midheight = (height as usize) / 2;
 
     if x == midwidth || y == midheight {
         None
@@ -110,11 +162,11 @@ fn determine_quadrant(x: usize, y: usize) -> Option<usize> {
     }
 }
 
-fn count_quadrants(map: &TileMap) -> usize {
+fn count_quadrants(map: &TileMap, width: isize, height: isize) -> usize {
     let mut counts = [0; 4];
 
     for ((x, y), robots) in map {
-        if let Some(i) = determine_quadrant(*x, *y) {
+        if let Some(i) = determine_quadrant(*x, *y, width, height) {
             counts[i] += robots.len()
         }
     }
@@ -123,23 +175,22 @@ fn count_quadrants(map: &TileMap) -> usize {
 }
 
 #[allow(dead_code)]
-fn show_map(map: &TileMap) {
-    for y in 0..HEIGHT as usize {
-        let mut line = String::new();
-        for x in 0..WIDTH as usize {
-            if map.contains_key(&(x, y)) {
-                line.push('*');
-            } else {
-                line.push(' ');
-            }
-        }
-        println!("{}", line);
+fn show_map(map: &TileMap, width: isize, height: isize) {
+    let mut grid = Grid::new(
+        width as usize,
+        height as usize,
+        vec![' '; (width * height) as usize],
+    );
+    for &(x, y) in map.keys() {
+        grid[Coord(x as isize, y as isize)] = '*';
     }
+
+    print!("{}", grid);
 }
 
-fn is_maybe_christmas_tree(map: &TileMap) -> bool {
-    for y in 0..HEIGHT as usize - 2 {
-        for x in 0..WIDTH as usize - 1 {
+fn is_maybe_christmas_tree(map: &TileMap, width: isize, height: isize) -> bool {
+    for y in 0..height as usize - 2 {
+        for x in 1..width as usize - 1 {
             /*
             Look for
                 *
@@ -161,3 +212,83 @@ fn is_maybe_christmas_tree(map: &TileMap) -> bool {
 
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3
+";
+
+    const EXAMPLE_WIDTH: isize = 11;
+    const EXAMPLE_HEIGHT: isize = 7;
+
+    #[test]
+    fn parses_robots() {
+        let robots = parse_input(EXAMPLE, EXAMPLE_WIDTH, EXAMPLE_HEIGHT).unwrap();
+
+        assert_eq!(robots.len(), 12);
+        assert_eq!(robots[0].position, (0, 4));
+        assert_eq!(robots[0].velocity, (3, -3));
+    }
+
+    #[test]
+    fn rejects_robots_that_would_leave_the_grid() {
+        let error = parse_input("p=11,0 v=1,1", EXAMPLE_WIDTH, EXAMPLE_HEIGHT).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(error.found, "`11`");
+
+        let error = parse_input("p=0,0 v=1,-7", EXAMPLE_WIDTH, EXAMPLE_HEIGHT).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 11));
+        assert_eq!(error.found, "`-7`");
+    }
+
+    #[test]
+    fn steps_with_wrapping() {
+        let mut map =
+            init_map(&parse_input("p=2,4 v=2,-3", EXAMPLE_WIDTH, EXAMPLE_HEIGHT).unwrap());
+        let mut positions = vec![];
+
+        for _ in 0..5 {
+            map = step(map, EXAMPLE_WIDTH, EXAMPLE_HEIGHT);
+            positions.push(*map.keys().next().unwrap());
+        }
+
+        assert_eq!(positions, [(4, 1), (6, 5), (8, 2), (10, 6), (1, 3)]);
+    }
+
+    #[test]
+    fn skips_middle_row_and_column() {
+        assert_eq!(
+            determine_quadrant(5, 0, EXAMPLE_WIDTH, EXAMPLE_HEIGHT),
+            None
+        );
+        assert_eq!(
+            determine_quadrant(0, 3, EXAMPLE_WIDTH, EXAMPLE_HEIGHT),
+            None
+        );
+        assert!(determine_quadrant(4, 2, EXAMPLE_WIDTH, EXAMPLE_HEIGHT).is_some());
+    }
+
+    #[test]
+    fn solves_example() {
+        let robots = parse_input(EXAMPLE, EXAMPLE_WIDTH, EXAMPLE_HEIGHT).unwrap();
+
+        assert_eq!(
+            safety_factor(&robots, EXAMPLE_WIDTH, EXAMPLE_HEIGHT, 100),
+            12
+        );
+    }
+}
@@ -1,14 +1,27 @@
-const INPUT: &str = include_str!("./input.in");
+pub struct Day15;
 
-pub fn run() {
-    part_one();
-    part_two();
+impl Solution for Day15 {
+    const DAY: usize = 15;
+    type Input<'a> = Input;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        part_one(input).into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        part_two(input).into()
+    }
 }
 
-type Warehouse = Vec<Vec<Cell>>;
+type Warehouse = Grid<Cell>;
+type Input = (Warehouse, Coord, Vec<Direction>);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Cell {
+pub enum Cell {
     Wall,
     Empty,
     Crate,
@@ -16,64 +29,64 @@ enum Cell {
     BigCrateRight,
 }
 
-use crate::util::Direction::{self, *};
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+    util::{
+        Coord,
+        Direction::{self, *},
+        Grid, Tile,
+    },
+};
 
-fn parse_input(input: &str) -> (Warehouse, (usize, usize), Vec<Direction>) {
-    let mut robot_pos = (0, 0);
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let input = input.trim();
 
-    let (warehouse_raw, directions_raw) = input.trim().split_once("\n\n").unwrap();
+    // The warehouse starts the input, so positions within it are positions
+    // within the whole input.
+    let (warehouse_raw, directions_raw) = parse::split_once(input, input, "\n\n")?;
 
-    let warehouse = warehouse_raw
-        .trim()
-        .lines()
-        .enumerate()
-        .map(|(line_no, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(col_no, c)| match c {
-                    '#' => Cell::Wall,
-                    '.' => Cell::Empty,
-                    'O' => Cell::Crate,
-                    '@' => {
-                        robot_pos = (line_no, col_no);
-                        Cell::Empty
-                    }
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect();
+    let (warehouse, markers) = Grid::parse_with(warehouse_raw, "one of `#.O@`", |c| match c {
+        '#' => Some(Tile::Cell(Cell::Wall)),
+        '.' => Some(Tile::Cell(Cell::Empty)),
+        'O' => Some(Tile::Cell(Cell::Crate)),
+        '@' => Some(Tile::Marker(Cell::Empty)),
+        _ => None,
+    })?;
+    let robot_pos = markers.require(warehouse_raw, '@', "a robot `@`")?;
 
     let directions = directions_raw
         .lines()
         .flat_map(|line| {
-            line.chars()
-                .map(|e| match e {
-                    '<' => Left,
-                    '^' => Up,
-                    'v' => Down,
-                    '>' => Right,
-                    _ => unreachable!(),
-                })
-                .collect::<Vec<_>>()
+            line.char_indices().map(move |(i, e)| match e {
+                '<' => Ok(Left),
+                '^' => Ok(Up),
+                'v' => Ok(Down),
+                '>' => Ok(Right),
+                _ => Err(ParseError::at(
+                    input,
+                    &line[i..i + e.len_utf8()],
+                    "one of `<^v>`",
+                )),
+            })
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    (warehouse, robot_pos, directions)
+    Ok((warehouse, robot_pos, directions))
 }
 
-fn try_move(dir: Direction, position: (usize, usize), warehouse: &mut Warehouse) -> bool {
-    let neighbor_pos = move_(position, dir);
-    match warehouse[neighbor_pos.0][neighbor_pos.1] {
+fn try_move(dir: Direction, position: Coord, warehouse: &mut Warehouse) -> bool {
+    let neighbor_pos = position.move_(dir);
+    match warehouse[neighbor_pos] {
         Cell::Empty => {
-            warehouse[neighbor_pos.0][neighbor_pos.1] = warehouse[position.0][position.1];
-            warehouse[position.0][position.1] = Cell::Empty;
+            warehouse[neighbor_pos] = warehouse[position];
+            warehouse[position] = Cell::Empty;
             true
         }
         Cell::Crate => {
             if try_move(dir, neighbor_pos, warehouse) {
-                warehouse[neighbor_pos.0][neighbor_pos.1] = warehouse[position.0][position.1];
-                warehouse[position.0][position.1] = Cell::Empty;
+                warehouse[neighbor_pos] = warehouse[position];
+                warehouse[position] = Cell::Empty;
                 true
             } else {
                 false
@@ -84,28 +97,24 @@ fn try_move(dir: Direction, position: (usize, usize), warehouse: &mut Warehouse)
     }
 }
 
-fn part_one() {
-    let (mut warehouse, mut position, directions) = parse_input(INPUT);
+fn part_one((warehouse, position, directions): &Input) -> usize {
+    let mut warehouse = warehouse.clone();
+    let mut position = *position;
 
-    for dir in directions {
+    for &dir in directions {
         if try_move(dir, position, &mut warehouse) {
-            position = match dir {
-                Up => (position.0 - 1, position.1),
-                Down => (position.0 + 1, position.1),
-                Left => (position.0, position.1 - 1),
-                Right => (position.0, position.1 + 1),
-            };
+            position = position.move_(dir);
         }
     }
 
-    println!("Part 1: {}", calc_gps(&warehouse, Cell::Crate));
+    calc_gps(&warehouse, Cell::Crate)
 }
 
 fn enlarge_warehouse(small_warehouse: Warehouse) -> Warehouse {
     let mut larger_warehouse = vec![];
-    for line in small_warehouse {
+    for line in small_warehouse.rows() {
         let mut larger_line = vec![];
-        for tile in line {
+        for &tile in line {
             if let Cell::Crate = tile {
                 larger_line.push(Cell::BigCrateLeft);
                 larger_line.push(Cell::BigCrateRight);
@@ -116,31 +125,22 @@ fn enlarge_warehouse(small_warehouse: Warehouse) -> Warehouse {
         }
         larger_warehouse.push(larger_line);
     }
-    larger_warehouse
-}
-
-fn move_((line, col): (usize, usize), dir: Direction) -> (usize, usize) {
-    match dir {
-        Up => (line - 1, col),
-        Down => (line + 1, col),
-        Left => (line, col - 1),
-        Right => (line, col + 1),
-    }
+    Grid::from_rows(larger_warehouse)
 }
 
 fn try_move_larger_crates(
     dir: Direction,
-    position: (usize, usize),
+    position: Coord,
     warehouse: &mut Warehouse,
     apply: bool,
 ) -> bool {
-    let neighbor_pos = move_(position, dir);
+    let neighbor_pos = position.move_(dir);
 
-    match warehouse[neighbor_pos.0][neighbor_pos.1] {
+    match warehouse[neighbor_pos] {
         Cell::Empty => {
             if apply {
-                warehouse[neighbor_pos.0][neighbor_pos.1] = warehouse[position.0][position.1];
-                warehouse[position.0][position.1] = Cell::Empty;
+                warehouse[neighbor_pos] = warehouse[position];
+                warehouse[position] = Cell::Empty;
             }
             true
         }
@@ -148,17 +148,11 @@ fn try_move_larger_crates(
         Cell::BigCrateLeft => match dir {
             Up | Down => {
                 if try_move_larger_crates(dir, neighbor_pos, warehouse, apply)
-                    & try_move_larger_crates(
-                        dir,
-                        (neighbor_pos.0, neighbor_pos.1 + 1),
-                        warehouse,
-                        apply,
-                    )
+                    & try_move_larger_crates(dir, neighbor_pos.move_(Right), warehouse, apply)
                 {
                     if apply {
-                        warehouse[neighbor_pos.0][neighbor_pos.1] =
-                            warehouse[position.0][position.1];
-                        warehouse[position.0][position.1] = Cell::Empty;
+                        warehouse[neighbor_pos] = warehouse[position];
+                        warehouse[position] = Cell::Empty;
                     }
                     true
                 } else {
@@ -168,9 +162,8 @@ fn try_move_larger_crates(
             _ => {
                 if try_move_larger_crates(dir, neighbor_pos, warehouse, apply) {
                     if apply {
-                        warehouse[neighbor_pos.0][neighbor_pos.1] =
-                            warehouse[position.0][position.1];
-                        warehouse[position.0][position.1] = Cell::Empty;
+                        warehouse[neighbor_pos] = warehouse[position];
+                        warehouse[position] = Cell::Empty;
                     }
                     true
                 } else {
@@ -181,17 +174,11 @@ fn try_move_larger_crates(
         Cell::BigCrateRight => match dir {
             Up | Down => {
                 if try_move_larger_crates(dir, neighbor_pos, warehouse, apply)
-                    & try_move_larger_crates(
-                        dir,
-                        (neighbor_pos.0, neighbor_pos.1 - 1),
-                        warehouse,
-                        apply,
-                    )
+                    & try_move_larger_crates(dir, neighbor_pos.move_(Left), warehouse, apply)
                 {
                     if apply {
-                        warehouse[neighbor_pos.0][neighbor_pos.1] =
-                            warehouse[position.0][position.1];
-                        warehouse[position.0][position.1] = Cell::Empty;
+                        warehouse[neighbor_pos] = warehouse[position];
+                        warehouse[position] = Cell::Empty;
                     }
                     true
                 } else {
@@ -201,9 +188,8 @@ fn try_move_larger_crates(
             _ => {
                 if try_move_larger_crates(dir, neighbor_pos, warehouse, apply) {
                     if apply {
-                        warehouse[neighbor_pos.0][neighbor_pos.1] =
-                            warehouse[position.0][position.1];
-                        warehouse[position.0][position.1] = Cell::Empty;
+                        warehouse[neighbor_pos] = warehouse[position];
+                        warehouse[position] = Cell::Empty;
                     }
                     true
                 } else {
@@ -215,45 +201,111 @@ fn try_move_larger_crates(
     }
 }
 
-fn part_two() {
-    let (warehouse, mut position, directions) = parse_input(INPUT);
-    let mut larger_warehouse = enlarge_warehouse(warehouse);
-    position.1 *= 2;
+fn part_two((warehouse, position, directions): &Input) -> usize {
+    let mut larger_warehouse = enlarge_warehouse(warehouse.clone());
+    let mut position = Coord(position.0 * 2, position.1);
 
-    for dir in directions {
+    for &dir in directions {
         if try_move_larger_crates(dir, position, &mut larger_warehouse, false) {
             try_move_larger_crates(dir, position, &mut larger_warehouse, true);
-            position = match dir {
-                Up => (position.0 - 1, position.1),
-                Down => (position.0 + 1, position.1),
-                Left => (position.0, position.1 - 1),
-                Right => (position.0, position.1 + 1),
-            };
+            position = position.move_(dir);
         }
     }
 
-    println!(
-        "Part 2: {}",
-        calc_gps(&larger_warehouse, Cell::BigCrateLeft)
-    );
+    calc_gps(&larger_warehouse, Cell::BigCrateLeft)
 }
 
 fn calc_gps(wh: &Warehouse, target_tile: Cell) -> usize {
     wh.iter()
-        .enumerate()
-        .map(|(y, line)| {
-            line.iter()
-                .enumerate()
-                .map(
-                    |(x, tile)| {
-                        if *tile == target_tile {
-                            100 * y + x
-                        } else {
-                            0
-                        }
-                    },
-                )
-                .sum::<usize>()
-        })
+        .filter(|&(_, &tile)| tile == target_tile)
+        .map(|(Coord(x, y), _)| 100 * y as usize + x as usize)
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_EXAMPLE: &str = "\
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<
+";
+
+    const LARGE_EXAMPLE: &str = "\
+##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+";
+
+    #[test]
+    fn parses_warehouse() {
+        let (warehouse, position, directions) = parse_input(SMALL_EXAMPLE).unwrap();
+
+        assert_eq!(warehouse.height(), 8);
+        assert_eq!(position, Coord(2, 2));
+        assert_eq!(warehouse[Coord(3, 1)], Cell::Crate);
+        assert_eq!(directions.len(), 15);
+        assert_eq!(directions[..3], [Left, Up, Up]);
+    }
+
+    #[test]
+    fn enlarges_warehouse() {
+        let (warehouse, _, _) = parse_input(SMALL_EXAMPLE).unwrap();
+        let larger = enlarge_warehouse(warehouse);
+
+        assert_eq!(larger.width(), 16);
+        assert_eq!(larger[Coord(6, 1)], Cell::BigCrateLeft);
+        assert_eq!(larger[Coord(7, 1)], Cell::BigCrateRight);
+    }
+
+    #[test]
+    fn rejects_unknown_cells_and_moves() {
+        let error = parse_input("####\n#.x@\n\n<>\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "one of `#.O@`");
+
+        let error = parse_input("####\n#.@#\n\n<>\n^x\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (5, 2));
+        assert_eq!(error.expected, "one of `<^v>`");
+    }
+
+    #[test]
+    fn solves_examples() {
+        let input = Day15::parse(SMALL_EXAMPLE).unwrap();
+
+        assert_eq!(Day15::part1(&input), Answer::Number(2028));
+
+        let input = Day15::parse(LARGE_EXAMPLE).unwrap();
+
+        assert_eq!(Day15::part1(&input), Answer::Number(10092));
+        assert_eq!(Day15::part2(&input), Answer::Number(9021));
+    }
+}
@@ -1,15 +1,30 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::HashSet;
 
-use crate::util::{Coord, Direction, Grid};
+use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+    util::{search, Coord, Direction, Grid, Tile},
+};
 
-const INPUT: &'static str = include_str!("./input.in");
+pub struct Day16;
 
-pub fn run() {
-    let maze = parse_input(INPUT);
-    let (min_cost, points) = dijkstra(&maze);
+impl Solution for Day16 {
+    const DAY: usize = 16;
+    /// The best score and the number of tiles on a best path, which both
+    /// parts share, so the search runs once while parsing.
+    type Input<'a> = (usize, usize);
 
-    println!("Part 1: {}", min_cost);
-    println!("Part 2: {}", points);
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input).map(|maze| dijkstra(&maze))
+    }
+
+    fn part1(&(score, _): &Self::Input<'_>) -> Answer {
+        score.into()
+    }
+
+    fn part2(&(_, tiles): &Self::Input<'_>) -> Answer {
+        tiles.into()
+    }
 }
 
 struct Maze {
@@ -24,155 +39,139 @@ enum Cell {
     Wall,
 }
 
-fn parse_input(input: &str) -> Maze {
-    let mut start = None;
-    let mut end = None;
-
-    let grid = Grid(
-        input
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(x, c)| match c {
-                        '#' => Cell::Wall,
-                        '.' => Cell::Empty,
-                        'S' => {
-                            start = Some(Coord(x as isize, y as isize));
-                            Cell::Empty
-                        }
-                        'E' => {
-                            end = Some(Coord(x as isize, y as isize));
-                            Cell::Empty
-                        }
-                        _ => unreachable!("{}", c),
-                    })
-                    .collect()
-            })
-            .collect(),
-    );
+fn parse_input(input: &str) -> Result<Maze, ParseError> {
+    let (grid, markers) = Grid::parse_with(input, "one of `#.SE`", |c| match c {
+        '#' => Some(Tile::Cell(Cell::Wall)),
+        '.' => Some(Tile::Cell(Cell::Empty)),
+        'S' | 'E' => Some(Tile::Marker(Cell::Empty)),
+        _ => None,
+    })?;
 
-    Maze {
+    Ok(Maze {
         grid,
-        start: start.expect("did not find start tile"),
-        end: end.expect("did not find end tile"),
-    }
+        start: markers.require(input, 'S', "a start tile `S`")?,
+        end: markers.require(input, 'E', "an end tile `E`")?,
+    })
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
-struct HeapEntry {
-    dist: usize,
-    location: Coord,
-    facing: Direction,
-}
+/// The cheapest score through the maze, and how many tiles are on any path
+/// with that score.
+fn dijkstra(maze: &Maze) -> (usize, usize) {
+    let paths = search::dijkstra((maze.start, Direction::Right), |&(location, facing)| {
+        Direction::all_directions()
+            .into_iter()
+            .filter(move |&dir| dir != facing.opposite())
+            .map(move |dir| {
+                let cost = if dir == facing { 1 } else { 1001 };
+
+                ((location.move_(dir), dir), cost)
+            })
+            .filter(|((neighbor, _), _)| maze.grid.get(*neighbor) == Some(&Cell::Empty))
+    });
 
-impl Ord for HeapEntry {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.dist.cmp(&other.dist)
-    }
+    let ends = Direction::all_directions().map(|dir| (maze.end, dir));
+    let shortest = ends
+        .iter()
+        .flat_map(|end| paths.distance(end))
+        .min()
+        .unwrap_or_else(|| {
+            panic!(
+                "did not find a path from {:?} to {:?}",
+                maze.start, maze.end
+            )
+        });
+
+    let points = paths
+        .on_shortest_paths(
+            ends.into_iter()
+                .filter(|end| paths.distance(end) == Some(shortest)),
+        )
+        .into_iter()
+        .map(|(location, _)| location)
+        .collect::<HashSet<_>>();
+
+    (shortest, points.len())
 }
 
-impl PartialOrd for HeapEntry {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+#[allow(dead_code)]
+fn show_best_tiles(maze: &Maze, tiles: &HashSet<Coord>) {
+    let render = maze
+        .grid
+        .render_with(|cell| match cell {
+            Cell::Wall => '#',
+            Cell::Empty => '.',
+        })
+        .highlight(tiles, 'O')
+        .marker(maze.start, 'S')
+        .marker(maze.end, 'E')
+        .colour(true);
+
+    print!("{}", render);
 }
 
-fn dijkstra(maze: &Maze) -> (usize, usize) {
-    let mut dist_from_start = HashMap::new();
-    dist_from_start.insert((maze.start, Direction::Right), 0);
-    let mut heap = BinaryHeap::new();
-    heap.extend([HeapEntry {
-        dist: 0,
-        facing: Direction::Right,
-        location: maze.start,
-    }]);
-
-    while let Some(HeapEntry {
-        location,
-        facing,
-        dist,
-    }) = heap.pop()
-    {
-        for dir in Direction::all_directions() {
-            let new_dist = if dir == facing {
-                dist + 1
-            } else if dir == facing.opposite() {
-                continue;
-            } else {
-                dist + 1001
-            };
-
-            let neighbor = location.move_(dir);
-            if maze.grid.get(neighbor) != Some(&Cell::Empty) {
-                continue;
-            }
-
-            if let Some(&old_dist) = dist_from_start.get(&(neighbor, dir)) {
-                if old_dist > new_dist {
-                    dist_from_start.insert((neighbor, dir), new_dist);
-                    heap.push(HeapEntry {
-                        location: neighbor,
-                        dist: new_dist,
-                        facing: dir,
-                    })
-                }
-            } else {
-                dist_from_start.insert((neighbor, dir), new_dist);
-                heap.push(HeapEntry {
-                    location: neighbor,
-                    dist: new_dist,
-                    facing: dir,
-                })
-            };
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST_EXAMPLE: &str = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+";
+
+    const SECOND_EXAMPLE: &str = "\
+#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################
+";
+
+    #[test]
+    fn parses_maze() {
+        let maze = parse_input(FIRST_EXAMPLE).unwrap();
+
+        assert_eq!(maze.start, Coord(1, 13));
+        assert_eq!(maze.end, Coord(13, 1));
+        assert_eq!(maze.grid.get(Coord(0, 0)), Some(&Cell::Wall));
+        assert_eq!(maze.grid.get(Coord(1, 1)), Some(&Cell::Empty));
     }
 
-    let shortest = *Direction::all_directions()
-        .iter()
-        .flat_map(|d| dist_from_start.get(&(maze.end, *d)))
-        .min()
-        .expect(&format!(
-            "did not find a path from {:?} to {:?}",
-            maze.start, maze.end
-        ));
-
-    let points = count_points_on_shortest_path(maze, &dist_from_start, shortest);
+    #[test]
+    fn finds_best_paths() {
+        assert_eq!(dijkstra(&parse_input(FIRST_EXAMPLE).unwrap()), (7036, 45));
+        assert_eq!(dijkstra(&parse_input(SECOND_EXAMPLE).unwrap()), (11048, 64));
+    }
 
-    (shortest, points)
-}
+    #[test]
+    fn solves_example() {
+        let input = Day16::parse(FIRST_EXAMPLE).unwrap();
 
-fn count_points_on_shortest_path(
-    maze: &Maze,
-    dists: &HashMap<(Coord, Direction), usize>,
-    cost: usize,
-) -> usize {
-    let mut path_points = HashSet::from([maze.end]);
-    let mut stack = vec![
-        (maze.end, Direction::Right, cost),
-        (maze.end, Direction::Up, cost),
-    ];
-
-    while let Some((loc, facing, cost)) = stack.pop() {
-        for dir in Direction::all_directions() {
-            if dir == facing.opposite() {
-                continue;
-            }
-
-            let neighbor = loc.move_(facing.opposite());
-            let Some(path_cost) = dists.get(&(neighbor, dir)) else {
-                continue;
-            };
-
-            let step_cost = if dir == facing { 1 } else { 1001 };
-
-            if path_cost + step_cost == cost {
-                path_points.insert(neighbor);
-                stack.push((neighbor, dir, *path_cost));
-            }
-        }
+        assert_eq!(Day16::part1(&input), Answer::Number(7036));
+        assert_eq!(Day16::part2(&input), Answer::Number(45));
     }
-
-    path_points.len()
 }
@@ -1,31 +1,46 @@
-const INPUT: &'static str = include_str!("./input.in");
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+};
 
-pub fn run() {
-    let mut computer = parse_input(INPUT);
+pub struct Day17;
 
-    computer.run();
+impl Solution for Day17 {
+    const DAY: usize = 17;
+    type Input<'a> = Computer;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(computer: &Self::Input<'_>) -> Answer {
+        let mut computer = computer.clone();
+        computer.run();
 
-    println!(
-        "Part 1: {}",
         computer
             .output
             .iter()
             .map(|o| format!("{}", o))
             .collect::<Vec<_>>()
             .join(",")
-    );
-
-    let instructions = computer.instructions.clone();
-    let mut options = vec![0];
-    for i in (0..instructions.len()).rev() {
-        options = computer.find_values_outputting(&instructions[i..instructions.len()], &options);
+            .into()
     }
 
-    println!("Part 2: {:?}", options.iter().min().unwrap());
+    fn part2(computer: &Self::Input<'_>) -> Answer {
+        let mut computer = computer.clone();
+        let instructions = computer.instructions.clone();
+        let mut options = vec![0];
+        for i in (0..instructions.len()).rev() {
+            options =
+                computer.find_values_outputting(&instructions[i..instructions.len()], &options);
+        }
+
+        (*options.iter().min().unwrap()).into()
+    }
 }
 
 #[derive(Debug, Clone)]
-struct Computer {
+pub struct Computer {
     instructions: Vec<u128>,
     a: u128,
     b: u128,
@@ -95,7 +110,7 @@ impl Computer {
     }
 
     fn bxc(&mut self, _: u128) {
-        self.b = self.b ^ self.c;
+        self.b ^= self.c;
     }
 
     fn out(&mut self, op: u128) {
@@ -119,7 +134,7 @@ impl Computer {
                 let option = (option << 3) | low_bits;
                 self.a = option;
                 self.run();
-                if &self.output[self.output.len() - target.len()..] == target {
+                if self.output.ends_with(target) {
                     values.push(option)
                 }
             }
@@ -129,33 +144,143 @@ impl Computer {
     }
 }
 
-fn parse_input(input: &str) -> Computer {
+/// The opcodes that take a combo operand, for which 7 is reserved.
+const COMBO_OPCODES: [u128; 5] = [0, 2, 5, 6, 7];
+
+fn parse_input(input: &str) -> Result<Computer, ParseError> {
     let mut lines = input.lines();
 
-    let a = register_value(lines.next().unwrap());
-    let b = register_value(lines.next().unwrap());
-    let c = register_value(lines.next().unwrap());
+    let a = register_value(input, &mut lines, "A")?;
+    let b = register_value(input, &mut lines, "B")?;
+    let c = register_value(input, &mut lines, "C")?;
 
     lines.next();
 
-    let instructions = lines
-        .next()
-        .unwrap()
-        .replace("Program: ", "")
+    let program = parse::next(input, input, &mut lines, "`Program: `")?;
+    let tokens = parse::strip_prefix(input, program, "Program: ")?
         .split(",")
-        .map(|c| c.parse().unwrap())
-        .collect();
+        .collect::<Vec<_>>();
+    let instructions = tokens
+        .iter()
+        .map(|token| parse::number(input, token))
+        .collect::<Result<Vec<u128>, _>>()?;
+
+    for (i, (&value, token)) in instructions.iter().zip(&tokens).enumerate() {
+        let is_combo = i % 2 == 1 && COMBO_OPCODES.contains(&instructions[i - 1]);
+
+        if value > 7 || (is_combo && value == 7) {
+            return Err(ParseError::at(input, token, "a 3-bit value"));
+        }
+    }
 
-    Computer {
+    Ok(Computer {
         a,
         b,
         c,
         instructions,
         ip: 0,
         output: vec![],
-    }
+    })
+}
+
+fn register_value<'a>(
+    input: &'a str,
+    lines: &mut impl Iterator<Item = &'a str>,
+    register: &str,
+) -> Result<u128, ParseError> {
+    let prefix = format!("Register {}: ", register);
+    let line = parse::next(input, input, lines, &format!("{:?}", prefix))?;
+
+    parse::number(input, parse::strip_prefix(input, line, &prefix)?.trim())
 }
 
-fn register_value(line: &str) -> u128 {
-    line.trim().split_once(": ").unwrap().1.parse().unwrap()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+";
+
+    const QUINE_EXAMPLE: &str = "\
+Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0
+";
+
+    fn computer(a: u128, b: u128, c: u128, instructions: &[u128]) -> Computer {
+        Computer {
+            instructions: instructions.to_vec(),
+            a,
+            b,
+            c,
+            ip: 0,
+            output: vec![],
+        }
+    }
+
+    #[test]
+    fn parses_computer() {
+        let computer = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!((computer.a, computer.b, computer.c), (729, 0, 0));
+        assert_eq!(computer.instructions, [0, 1, 5, 4, 3, 0]);
+    }
+
+    #[test]
+    fn rejects_invalid_programs() {
+        let error = parse_input("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 0,9\n")
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (5, 12));
+        assert_eq!(error.expected, "a 3-bit value");
+
+        let error =
+            parse_input("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 1,7,5,7\n")
+                .unwrap_err();
+        assert_eq!((error.line, error.column), (5, 16));
+    }
+
+    #[test]
+    fn runs_small_programs() {
+        let mut c = computer(0, 0, 9, &[2, 6]);
+        c.run();
+        assert_eq!(c.b, 1);
+
+        let mut c = computer(10, 0, 0, &[5, 0, 5, 1, 5, 4]);
+        c.run();
+        assert_eq!(c.output, [0, 1, 2]);
+
+        let mut c = computer(2024, 0, 0, &[0, 1, 5, 4, 3, 0]);
+        c.run();
+        assert_eq!(c.output, [4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+        assert_eq!(c.a, 0);
+
+        let mut c = computer(0, 29, 0, &[1, 7]);
+        c.run();
+        assert_eq!(c.b, 26);
+
+        let mut c = computer(0, 2024, 43690, &[4, 0]);
+        c.run();
+        assert_eq!(c.b, 44354);
+    }
+
+    #[test]
+    fn solves_examples() {
+        let input = Day17::parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            Day17::part1(&input),
+            Answer::Text("4,6,3,5,6,3,5,2,1,0".to_string())
+        );
+
+        let input = Day17::parse(QUINE_EXAMPLE).unwrap();
+
+        assert_eq!(Day17::part2(&input), Answer::Number(117440));
+    }
 }
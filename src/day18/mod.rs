@@ -1,47 +1,82 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+    util::{region, search, Coord, Grid},
+};
 
-use crate::util::{Coord, Direction, Grid};
+pub struct Day18;
 
-const INPUT: &'static str = include_str!("./input.in");
+impl Solution for Day18 {
+    const DAY: usize = 18;
+    type Input<'a> = Vec<Coord>;
 
-const WIDTH: usize = 71;
-const HEIGHT: usize = 71;
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(falling_bytes: &Self::Input<'_>) -> Answer {
+        shortest_path_after(falling_bytes, SIZE, FALLEN).into()
+    }
+
+    fn part2(falling_bytes: &Self::Input<'_>) -> Answer {
+        let Coord(x, y) = first_blocking_byte(falling_bytes, SIZE, FALLEN);
+
+        format!("{},{}", x, y).into()
+    }
+}
+
+/// The memory space is `SIZE` by `SIZE`, and part 1 looks at it after the
+/// first `FALLEN` bytes have landed.
+const SIZE: usize = 71;
+const FALLEN: usize = 1024;
+
+const START: Coord = Coord(0, 0);
+
+fn exit(size: usize) -> Coord {
+    Coord(size as isize - 1, size as isize - 1)
+}
+
+fn shortest_path_after(falling_bytes: &[Coord], size: usize, fallen: usize) -> usize {
+    let grid = construct_map(&falling_bytes[0..fallen], size);
+
+    shortest_path(&grid, START, exit(size))
+}
+
+fn first_blocking_byte(falling_bytes: &[Coord], size: usize, fallen: usize) -> Coord {
+    let grid = construct_map(&falling_bytes[0..fallen], size);
 
-pub fn run() {
-    let start = Coord(0, 0);
-    let end = Coord(WIDTH as isize - 1, HEIGHT as isize - 1);
-    let falling_bytes = parse_input(INPUT);
-    let bytes_part1 = &falling_bytes[0..1024];
-    let grid = construct_map(bytes_part1);
-    let shortest_path = dijkstra(&grid, start, end);
-    println!("Part 1: {}", shortest_path);
+    let raining_bytes = &falling_bytes[fallen..];
+    let blocker = region::first_disconnecting(
+        &grid,
+        |&cell| cell == Cell::Empty,
+        START,
+        exit(size),
+        raining_bytes,
+    );
 
-    let Coord(x, y) = first_blocker(grid, start, end, &falling_bytes[1024..]);
-    println!("Part 2: {},{}", x, y);
+    raining_bytes[blocker.expect("Path was never blocked!")]
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 enum Cell {
+    #[default]
     Empty,
     Corrupted,
 }
 
-impl Default for Cell {
-    fn default() -> Self {
-        Cell::Empty
-    }
-}
-
-fn parse_input(input: &str) -> Vec<Coord> {
+fn parse_input(input: &str) -> Result<Vec<Coord>, ParseError> {
     input
         .lines()
-        .map(|line| line.split_once(",").unwrap())
-        .map(|(a, b)| Coord(a.parse().unwrap(), b.parse().unwrap()))
+        .map(|line| {
+            let (a, b) = parse::split_once(input, line, ",")?;
+
+            Ok(Coord(parse::number(input, a)?, parse::number(input, b)?))
+        })
         .collect()
 }
 
-fn construct_map(falling_bytes: &[Coord]) -> Grid<Cell> {
-    let mut grid = Grid::with_dimensions(WIDTH, HEIGHT);
+fn construct_map(falling_bytes: &[Coord], size: usize) -> Grid<Cell> {
+    let mut grid = Grid::with_dimensions(size, size);
 
     for byte in falling_bytes {
         grid.set(*byte, Cell::Corrupted);
@@ -50,95 +85,79 @@ fn construct_map(falling_bytes: &[Coord]) -> Grid<Cell> {
     grid
 }
 
-#[derive(PartialEq, Eq)]
-struct HeapEntry {
-    location: Coord,
-    dist: usize,
-}
-
-impl Ord for HeapEntry {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.dist.cmp(&other.dist)
-    }
-}
-
-impl PartialOrd for HeapEntry {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+fn shortest_path(grid: &Grid<Cell>, start: Coord, end: Coord) -> usize {
+    let paths = search::bfs(start, |location| {
+        location
+            .adjacent()
+            .into_iter()
+            .filter(|&neighbor| grid.get(neighbor) == Some(&Cell::Empty))
+    });
+
+    paths
+        .distance(&end)
+        .unwrap_or_else(|| panic!("did not find a path from {:?} to {:?}", start, end))
 }
 
-fn dijkstra(grid: &Grid<Cell>, start: Coord, end: Coord) -> usize {
-    let mut dist_from_start = HashMap::new();
-    dist_from_start.insert(start, 0);
-    let mut heap = BinaryHeap::from([HeapEntry {
-        location: start,
-        dist: 0,
-    }]);
-
-    while let Some(HeapEntry { location, dist }) = heap.pop() {
-        for dir in Direction::all_directions() {
-            let new_dist = dist + 1;
-            let neighbor = location.move_(dir);
-            if grid.get(neighbor) != Some(&Cell::Empty) {
-                continue;
-            }
-
-            if let Some(&old_dist) = dist_from_start.get(&neighbor) {
-                if old_dist > new_dist {
-                    dist_from_start.insert(neighbor, new_dist);
-                    heap.push(HeapEntry {
-                        location: neighbor,
-                        dist: new_dist,
-                    })
-                }
-            } else {
-                dist_from_start.insert(neighbor, new_dist);
-                heap.push(HeapEntry {
-                    location: neighbor,
-                    dist: new_dist,
-                })
-            };
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+";
+
+    const EXAMPLE_SIZE: usize = 7;
+    const EXAMPLE_FALLEN: usize = 12;
+
+    #[test]
+    fn parses_bytes() {
+        let bytes = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(bytes.len(), 25);
+        assert_eq!(bytes[0], Coord(5, 4));
     }
 
-    *dist_from_start.get(&end).expect(&format!(
-        "did not find a path from {:?} to {:?}",
-        start, end
-    ))
-}
+    #[test]
+    fn finds_shortest_path() {
+        let bytes = parse_input(EXAMPLE).unwrap();
 
-fn reachable(grid: &Grid<Cell>, start: Coord, end: Coord) -> bool {
-    let mut visited = HashSet::new();
-    let mut stack = vec![start];
-    while let Some(next) = stack.pop() {
-        if visited.contains(&next) {
-            continue;
-        }
-        visited.insert(next);
-
-        if next == end {
-            return true;
-        }
-
-        stack.extend(
-            Direction::all_directions()
-                .into_iter()
-                .map(|d| next.move_(d))
-                .filter(|loc| grid.get(*loc) == Some(&Cell::Empty)),
-        )
+        assert_eq!(
+            shortest_path_after(&bytes, EXAMPLE_SIZE, EXAMPLE_FALLEN),
+            22
+        );
     }
 
-    false
-}
+    #[test]
+    fn finds_first_blocking_byte() {
+        let bytes = parse_input(EXAMPLE).unwrap();
 
-fn first_blocker(mut grid: Grid<Cell>, start: Coord, end: Coord, raining_bytes: &[Coord]) -> Coord {
-    for byte in raining_bytes {
-        grid.set(*byte, Cell::Corrupted);
-        if !reachable(&grid, start, end) {
-            return *byte;
-        }
+        assert_eq!(
+            first_blocking_byte(&bytes, EXAMPLE_SIZE, EXAMPLE_FALLEN),
+            Coord(6, 1)
+        );
     }
-
-    panic!("Path was never blocked!")
 }
@@ -1,43 +1,65 @@
 use std::collections::HashMap;
 
-const INPUT: &'static str = include_str!("./input.in");
-
-pub fn run() {
-    let Input { towels, patterns } = parse_input(INPUT);
-    let prefix_tree = construct_prefix_tree(&towels);
-    let possible = patterns
-        .iter()
-        .filter(|pat| has_total_match(pat, &prefix_tree))
-        .count();
-    println!("Part 1: {}", possible);
-
-    let mut known_counts = HashMap::new();
-
-    let total_matches: usize = patterns
-        .iter()
-        .map(|pat| count_total_matches(pat, &prefix_tree, &mut known_counts))
-        .sum();
-    println!("Part 2: {}", total_matches);
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+};
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: usize = 19;
+    type Input<'a> = Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(Input { towels, patterns }: &Self::Input<'_>) -> Answer {
+        let prefix_tree = construct_prefix_tree(towels);
+
+        patterns
+            .iter()
+            .filter(|pat| has_total_match(pat, &prefix_tree))
+            .count()
+            .into()
+    }
+
+    fn part2(Input { towels, patterns }: &Self::Input<'_>) -> Answer {
+        let prefix_tree = construct_prefix_tree(towels);
+        let mut known_counts = HashMap::new();
+
+        patterns
+            .iter()
+            .map(|pat| count_total_matches(pat, &prefix_tree, &mut known_counts))
+            .sum::<usize>()
+            .into()
+    }
 }
 
-struct Input<'a> {
+pub struct Input<'a> {
     towels: Vec<&'a str>,
     patterns: Vec<&'a str>,
 }
 
-fn parse_input<'a>(input: &'a str) -> Input<'a> {
+fn parse_input<'a>(input: &'a str) -> Result<Input<'a>, ParseError> {
     let mut lines = input.lines();
 
-    let towels = lines.next().unwrap().split(", ").collect();
+    let towels = parse::next(input, input, &mut lines, "a list of towels")?
+        .split(", ")
+        .collect();
 
-    lines.next().unwrap();
+    let blank = parse::next(input, input, &mut lines, "a blank line")?;
+    if !blank.is_empty() {
+        return Err(ParseError::at(input, blank, "a blank line"));
+    }
 
     let patterns = lines.collect();
 
-    Input { towels, patterns }
+    Ok(Input { towels, patterns })
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct PrefixTree {
     end: bool,
     children: HashMap<char, PrefixTree>,
@@ -62,15 +84,6 @@ impl PrefixTree {
     }
 }
 
-impl Default for PrefixTree {
-    fn default() -> Self {
-        Self {
-            end: false,
-            children: HashMap::new(),
-        }
-    }
-}
-
 fn construct_prefix_tree(towels: &[&str]) -> PrefixTree {
     let mut tree = PrefixTree {
         end: false,
@@ -112,7 +125,7 @@ fn count_total_matches<'a>(
     }
 
     let mut count = 0;
-    for match_len in prefix_tree.all_match_lengths(&pattern) {
+    for match_len in prefix_tree.all_match_lengths(pattern) {
         if match_len == pattern.len() {
             count += 1
         } else if match_len > 0 {
@@ -124,3 +137,68 @@ fn count_total_matches<'a>(
 
     count
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb
+";
+
+    #[test]
+    fn parses_towels_and_patterns() {
+        let input = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(input.towels, ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"]);
+        assert_eq!(input.patterns.len(), 8);
+        assert_eq!(input.patterns[0], "brwrr");
+    }
+
+    #[test]
+    fn finds_prefix_match_lengths() {
+        let tree = construct_prefix_tree(&["r", "rb", "rbw"]);
+
+        assert_eq!(tree.all_match_lengths("rbwr"), [1, 2, 3]);
+        assert_eq!(tree.all_match_lengths("rwb"), [1]);
+        assert!(tree.all_match_lengths("b").is_empty());
+    }
+
+    #[test]
+    fn matches_patterns() {
+        let input = parse_input(EXAMPLE).unwrap();
+        let tree = construct_prefix_tree(&input.towels);
+
+        let matches = input
+            .patterns
+            .iter()
+            .map(|p| has_total_match(p, &tree))
+            .collect::<Vec<_>>();
+        assert_eq!(matches, [true, true, true, true, false, true, true, false]);
+
+        let mut known_counts = HashMap::new();
+        let counts = input
+            .patterns
+            .iter()
+            .map(|p| count_total_matches(p, &tree, &mut known_counts))
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [2, 1, 4, 6, 0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day19::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day19::part1(&input), Answer::Number(6));
+        assert_eq!(Day19::part2(&input), Answer::Number(16));
+    }
+}
@@ -1,13 +1,28 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::util::{Coord, Direction, Grid};
+use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+    util::{Coord, Direction, Grid, Tile},
+};
 
-const INPUT: &'static str = include_str!("./input.in");
+pub struct Day20;
 
-pub fn run() {
-    let track = parse_input(INPUT);
-    println!("Part 1: {}", find_cheats(&track).len());
-    println!("Part 2: {}", find_cheats_p2(&track, 100).len());
+impl Solution for Day20 {
+    const DAY: usize = 20;
+    type Input<'a> = Track;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(track: &Self::Input<'_>) -> Answer {
+        find_cheats(track, 100).len().into()
+    }
+
+    fn part2(track: &Self::Input<'_>) -> Answer {
+        find_cheats_p2(track, 100).len().into()
+    }
 }
 
 #[derive(Debug)]
@@ -17,46 +32,33 @@ enum Cell {
 }
 
 #[derive(Debug)]
-struct Track {
+pub struct Track {
     path: Vec<Coord>,
 }
 
-fn parse_input(input: &str) -> Track {
-    let mut start = None;
-    let mut end = None;
-    let map = Grid(
-        input
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(x, c)| match c {
-                        'S' => {
-                            start = Some(Coord(x as isize, y as isize));
-                            Cell::Empty
-                        }
-                        'E' => {
-                            end = Some(Coord(x as isize, y as isize));
-                            Cell::Empty
-                        }
-                        '#' => Cell::Wall,
-                        '.' => Cell::Empty,
-                        _ => unreachable!(),
-                    })
-                    .collect()
-            })
-            .collect(),
-    );
-
-    let start = start.expect("did not find start");
-    let end = end.expect("did not find end");
-    let path = get_path(&map, start, end);
-
-    Track { path }
+fn parse_input(input: &str) -> Result<Track, ParseError> {
+    let (map, markers) = Grid::parse_with(input, "one of `#.SE`", |c| match c {
+        'S' | 'E' => Some(Tile::Marker(Cell::Empty)),
+        '#' => Some(Tile::Cell(Cell::Wall)),
+        '.' => Some(Tile::Cell(Cell::Empty)),
+        _ => None,
+    })?;
+
+    let start = markers.require(input, 'S', "a start tile `S`")?;
+    let end = markers.require(input, 'E', "an end tile `E`")?;
+    let path = get_path(input, &map, start, end)?;
+
+    Ok(Track { path })
 }
 
-fn get_path(map: &Grid<Cell>, start: Coord, end: Coord) -> Vec<Coord> {
+/// Follows the single track from `start` to `end`, failing at the first dead
+/// end.
+fn get_path(
+    input: &str,
+    map: &Grid<Cell>,
+    start: Coord,
+    end: Coord,
+) -> Result<Vec<Coord>, ParseError> {
     let mut curr = start;
     let mut prev_dir = None;
     let mut path = vec![start];
@@ -80,30 +82,35 @@ fn get_path(map: &Grid<Cell>, start: Coord, end: Coord) -> Vec<Coord> {
             }
         }
 
-        for direction in Direction::all_directions() {
-            let next = curr.move_(direction);
-            if path.contains(&next) {
-                println!("{:?} is already in the path!", next);
-            } else {
-                println!("{:?} = {:?}", next, map.get(next));
-            }
-        }
-
-        panic!("{:?}", curr);
+        return Err(ParseError::at(
+            input,
+            cell_at(input, curr),
+            "a track leading on to `E`",
+        ));
     }
 
-    path
+    Ok(path)
+}
+
+/// The character at `coord` in the grid `input`, as a slice of it.
+fn cell_at(input: &str, Coord(x, y): Coord) -> &str {
+    let line = input.lines().nth(y as usize).unwrap_or_default();
+    match line.char_indices().nth(x as usize) {
+        Some((i, c)) => &line[i..i + c.len_utf8()],
+        None => &line[line.len()..],
+    }
 }
 
 fn reverse_index(path: &[Coord]) -> HashMap<Coord, usize> {
     path.iter().enumerate().map(|(i, c)| (*c, i)).collect()
 }
 
-fn find_cheats(track: &Track) -> HashSet<(Coord, Coord)> {
+fn find_cheats(track: &Track, min_save: usize) -> HashSet<(Coord, Coord)> {
     let reverse_index = reverse_index(&track.path);
     let mut good_cheats = HashSet::new();
 
-    for (i, loc) in track.path[..track.path.len() - 100].iter().enumerate() {
+    let cheat_starts = track.path.len().saturating_sub(min_save);
+    for (i, loc) in track.path[..cheat_starts].iter().enumerate() {
         for dir1 in Direction::all_directions() {
             for dir2 in Direction::all_directions() {
                 if dir2 == dir1.opposite() {
@@ -115,7 +122,8 @@ fn find_cheats(track: &Track) -> HashSet<(Coord, Coord)> {
                     continue;
                 };
 
-                if next_i > i && next_i - i > 100 {
+                // The cheat itself takes 2 picoseconds
+                if next_i > i && next_i - i >= min_save + 2 {
                     good_cheats.insert((*loc, next));
                 }
             }
@@ -127,7 +135,8 @@ fn find_cheats(track: &Track) -> HashSet<(Coord, Coord)> {
 
 fn find_cheats_p2(track: &Track, min_save: usize) -> HashSet<(Coord, Coord)> {
     let mut good_cheats = HashSet::new();
-    for (i, loc) in track.path[..track.path.len() - min_save].iter().enumerate() {
+    let cheat_starts = track.path.len().saturating_sub(min_save);
+    for (i, loc) in track.path[..cheat_starts].iter().enumerate() {
         for (j, next_loc) in track.path[i + min_save..].iter().enumerate() {
             let dist = next_loc.manhattan_distance(*loc);
             if next_loc.manhattan_distance(*loc) <= 20 && j >= dist {
@@ -138,3 +147,60 @@ fn find_cheats_p2(track: &Track, min_save: usize) -> HashSet<(Coord, Coord)> {
 
     good_cheats
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+    #[test]
+    fn follows_track() {
+        let track = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(track.path.len(), 85);
+        assert_eq!(track.path[0], Coord(1, 3));
+        assert_eq!(track.path[84], Coord(5, 7));
+    }
+
+    #[test]
+    fn rejects_dead_ends() {
+        let error = parse_input("S.#\n###\n..E\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (1, 2));
+        assert_eq!(error.found, "`.`");
+    }
+
+    #[test]
+    fn finds_two_step_cheats() {
+        let track = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(find_cheats(&track, 64).len(), 1);
+        assert_eq!(find_cheats(&track, 40).len(), 2);
+        assert_eq!(find_cheats(&track, 20).len(), 5);
+    }
+
+    #[test]
+    fn finds_long_cheats() {
+        let track = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(find_cheats_p2(&track, 76).len(), 3);
+        assert_eq!(find_cheats_p2(&track, 50).len(), 285);
+    }
+}
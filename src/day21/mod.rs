@@ -1,31 +1,43 @@
-use std::{collections::HashMap, iter, usize};
+use std::{collections::HashMap, iter};
 
-use crate::util::{permutations, Coord, Direction};
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+    util::{permutations, Coord, Direction},
+};
 
-const INPUT: &'static str = include_str!("./input.in");
+pub struct Day21;
 
-pub fn run() {
-    let sequences = parse_input(INPUT);
+impl Solution for Day21 {
+    const DAY: usize = 21;
+    type Input<'a> = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(sequences: &Self::Input<'_>) -> Answer {
+        complexity(sequences, 3).into()
+    }
+
+    fn part2(sequences: &Self::Input<'_>) -> Answer {
+        complexity(sequences, 26).into()
+    }
+}
+
+fn complexity(sequences: &[Vec<char>], robots: usize) -> usize {
     let mut cache = HashMap::new();
-    println!(
-        "Part 1: {}",
-        sequences
-            .iter()
-            .map(|seq| parse_code(&seq) * count_button_presses(&seq, 0, 3, &mut cache))
-            .sum::<usize>()
-    );
 
-    println!(
-        "Part 1: {}",
-        sequences
-            .iter()
-            .map(|seq| parse_code(&seq) * count_button_presses(&seq, 0, 26, &mut cache))
-            .sum::<usize>()
-    );
+    sequences
+        .iter()
+        .map(|seq| parse_code(seq) * count_button_presses(seq, 0, robots, &mut cache))
+        .sum()
 }
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+fn parse_input(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    parse::grid(input, "a digit or `A`", |c, _, _| {
+        (c.is_ascii_digit() || c == 'A').then_some(c)
+    })
 }
 
 fn parse_code(code: &[char]) -> usize {
@@ -91,7 +103,7 @@ fn count_button_presses(
                 })
                 .map(|p| {
                     p.push('A');
-                    count_button_presses(&p, depth + 1, limit, seen)
+                    count_button_presses(p, depth + 1, limit, seen)
                 })
                 .min()
                 .unwrap();
@@ -134,3 +146,46 @@ fn directionpad_to_coord(button: char) -> Coord {
         _ => unreachable!(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+029A
+980A
+179A
+456A
+379A
+";
+
+    #[test]
+    fn parses_codes() {
+        let codes = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(codes[0], ['0', '2', '9', 'A']);
+        assert_eq!(
+            codes.iter().map(|c| parse_code(c)).collect::<Vec<_>>(),
+            [29, 980, 179, 456, 379]
+        );
+    }
+
+    #[test]
+    fn counts_button_presses() {
+        let lengths = parse_input(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(|code| count_button_presses(code, 0, 3, &mut HashMap::new()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(lengths, [68, 60, 68, 64, 64]);
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day21::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day21::part1(&input), Answer::Number(126384));
+        assert_eq!(Day21::part2(&input), Answer::Number(154115708116294));
+    }
+}
@@ -1,43 +1,60 @@
-use std::collections::HashMap;
-
-const INPUT: &'static str = include_str!("./input.in");
-
-pub fn run() {
-    let input = parse_input(&INPUT);
-    println!(
-        "Part 1: {}",
-        input.iter().map(|n| evolve_2000(*n)).sum::<usize>()
-    );
-    let mut all_sequences = vec![];
-    for n in input {
-        all_sequences.push(sequences(&price_changes(n)));
+use std::collections::HashSet;
+
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+    util::Counter,
+};
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: usize = 22;
+    type Input<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
     }
 
-    println!("Part 2: {}", find_best_sequence(&all_sequences));
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        input.iter().map(|n| evolve_2000(*n)).sum::<usize>().into()
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Answer {
+        let all_sequences = input
+            .iter()
+            .map(|n| sequences(&price_changes(*n)))
+            .collect::<Vec<_>>();
+
+        find_best_sequence(&all_sequences).into()
+    }
 }
 
-fn find_best_sequence(all_price_changes: &[Vec<((isize, isize, isize, isize), isize)>]) -> isize {
-    let mut hm: HashMap<_, isize> = HashMap::new();
+type Sequence = (isize, isize, isize, isize);
+
+/// The most bananas any one sequence of price changes can get, selling to
+/// each buyer the first time it comes up.
+fn find_best_sequence(all_price_changes: &[Vec<(Sequence, isize)>]) -> usize {
+    let mut bananas = Counter::new();
 
     for price_changes in all_price_changes {
-        let mut hm2: HashMap<_, isize> = HashMap::new();
+        let mut seen = HashSet::new();
 
-        for (seq, price) in price_changes {
-            if !hm2.contains_key(seq) {
-                hm2.insert(seq, *price);
+        for &(seq, price) in price_changes {
+            if seen.insert(seq) {
+                bananas.count_n(seq, price as usize);
             }
         }
-
-        for (k, v) in hm2 {
-            *hm.entry(*k).or_default() += v
-        }
     }
 
-    *hm.iter().max_by_key(|(_, v)| *v).unwrap().1
+    bananas.max_by_count().map_or(0, |(_, total)| total)
 }
 
-fn parse_input(input: &str) -> Vec<usize> {
-    input.lines().map(|line| line.parse().unwrap()).collect()
+fn parse_input(input: &str) -> Result<Vec<usize>, ParseError> {
+    input
+        .lines()
+        .map(|line| parse::number(input, line))
+        .collect()
 }
 
 fn evolve_number(number: usize) -> usize {
@@ -74,7 +91,7 @@ fn price_changes(number: usize) -> Vec<(isize, isize)> {
     price_changes
 }
 
-fn sequences(seq: &[(isize, isize)]) -> Vec<((isize, isize, isize, isize), isize)> {
+fn sequences(seq: &[(isize, isize)]) -> Vec<(Sequence, isize)> {
     seq.iter()
         .zip(seq.iter().skip(1))
         .zip(seq.iter().skip(2))
@@ -82,3 +99,53 @@ fn sequences(seq: &[(isize, isize)]) -> Vec<((isize, isize, isize, isize), isize
         .map(|(((a, b), c), d)| ((a.0, b.0, c.0, d.0), d.1))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evolves_secret_numbers() {
+        let secrets = (0..10)
+            .scan(123, |n, _| {
+                *n = evolve_number(*n);
+                Some(*n)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            secrets,
+            [
+                15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
+                5908254
+            ]
+        );
+    }
+
+    #[test]
+    fn evolves_2000_times() {
+        assert_eq!(evolve_2000(1), 8685429);
+        assert_eq!(evolve_2000(10), 4700978);
+        assert_eq!(evolve_2000(100), 15273692);
+        assert_eq!(evolve_2000(2024), 8667524);
+    }
+
+    #[test]
+    fn tracks_price_changes() {
+        let changes = price_changes(123);
+
+        assert_eq!(changes[..4], [(-3, 0), (6, 6), (-1, 5), (-1, 4)]);
+        assert_eq!(sequences(&changes)[0], ((-3, 6, -1, -1), 4));
+    }
+
+    #[test]
+    fn solves_examples() {
+        let input = Day22::parse("1\n10\n100\n2024\n").unwrap();
+
+        assert_eq!(Day22::part1(&input), Answer::Number(37327623));
+
+        let input = Day22::parse("1\n2\n3\n2024\n").unwrap();
+
+        assert_eq!(Day22::part2(&input), Answer::Number(23));
+    }
+}
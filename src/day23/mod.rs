@@ -1,42 +1,56 @@
 use std::collections::{HashMap, HashSet};
 
-const INPUT: &'static str = include_str!("./input.in");
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+};
 
-pub fn run() {
-    let connections = parse_input(INPUT);
-    println!(
-        "Part 1: {}",
-        find_trios(&connections)
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: usize = 23;
+    type Input<'a> = ConnectionMap<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(connections: &Self::Input<'_>) -> Answer {
+        find_trios(connections)
             .iter()
             .filter(|(a, b, c)| a.contains('t') || b.contains('t') || c.contains('t'))
             .count()
-    );
-
-    let mut max_clique = find_max_clique(
-        &connections,
-        HashSet::new(),
-        connections.keys().map(|c| *c).collect(),
-        HashSet::new(),
-    )
-    .into_iter()
-    .collect::<Vec<_>>();
-
-    max_clique.sort();
-    println!("Part 2: {}", max_clique.join(","));
+            .into()
+    }
+
+    fn part2(connections: &Self::Input<'_>) -> Answer {
+        let mut max_clique = find_max_clique(
+            connections,
+            HashSet::new(),
+            connections.keys().copied().collect(),
+            HashSet::new(),
+        )
+        .into_iter()
+        .collect::<Vec<_>>();
+
+        max_clique.sort();
+
+        max_clique.join(",").into()
+    }
 }
 
 type ConnectionMap<'a> = HashMap<&'a str, HashSet<&'a str>>;
 
-fn parse_input<'a>(input: &'a str) -> ConnectionMap<'a> {
+fn parse_input<'a>(input: &'a str) -> Result<ConnectionMap<'a>, ParseError> {
     let mut connections: HashMap<&str, HashSet<&str>> = HashMap::new();
 
     for line in input.lines() {
-        let (a, b) = line.split_once('-').unwrap();
+        let (a, b) = parse::split_once(input, line, "-")?;
         connections.entry(a).or_default().insert(b);
         connections.entry(b).or_default().insert(a);
     }
 
-    connections
+    Ok(connections)
 }
 
 fn find_trios<'a>(connections: &ConnectionMap<'a>) -> HashSet<(&'a str, &'a str, &'a str)> {
@@ -74,7 +88,7 @@ fn find_max_clique<'a>(
     mut consider: HashSet<&'a str>,
     mut exclude: HashSet<&'a str>,
 ) -> HashSet<&'a str> {
-    if consider.len() == 0 && exclude.len() == 0 {
+    if consider.is_empty() && exclude.is_empty() {
         return clique;
     }
 
@@ -89,11 +103,11 @@ fn find_max_clique<'a>(
             clique,
             consider
                 .intersection(connections.get(v).unwrap())
-                .map(|c| *c)
+                .copied()
                 .collect(),
             exclude
                 .intersection(connections.get(v).unwrap())
-                .map(|c| *c)
+                .copied()
                 .collect(),
         );
         if cc.len() > max.len() {
@@ -106,3 +120,71 @@ fn find_max_clique<'a>(
 
     max
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn
+";
+
+    #[test]
+    fn parses_connections_both_ways() {
+        let connections = parse_input(EXAMPLE).unwrap();
+
+        assert!(connections["kh"].contains("tc"));
+        assert!(connections["tc"].contains("kh"));
+        assert_eq!(connections.len(), 16);
+    }
+
+    #[test]
+    fn finds_trios_with_t() {
+        let trios = find_trios(&parse_input(EXAMPLE).unwrap());
+
+        assert_eq!(trios.len(), 7);
+        assert!(trios.contains(&("co", "de", "ta")));
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day23::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day23::part1(&input), Answer::Number(7));
+        assert_eq!(
+            Day23::part2(&input),
+            Answer::Text("co,de,ka,ta".to_string())
+        );
+    }
+}
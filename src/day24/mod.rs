@@ -1,27 +1,44 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
 
-const INPUT: &'static str = include_str!("./input.in");
+use crate::{
+    parse::{self, ParseError},
+    solution::{Answer, Solution},
+};
 
-pub fn run() {
-    let input = parse_input(INPUT);
-    println!("Part 1: {}", simulate(&input));
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: usize = 24;
+    type Input<'a> = Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        simulate(input).into()
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 enum GateType {
-    AND,
-    OR,
-    XOR,
+    And,
+    Or,
+    Xor,
 }
 
 impl FromStr for GateType {
-    type Err = ();
+    type Err = ParseError;
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         Ok(match value {
-            "AND" => GateType::AND,
-            "OR" => GateType::OR,
-            "XOR" => GateType::XOR,
-            _ => return Err(()),
+            "AND" => GateType::And,
+            "OR" => GateType::Or,
+            "XOR" => GateType::Xor,
+            _ => return Err(ParseError::at(value, value, "one of `AND`, `OR`, `XOR`")),
         })
     }
 }
@@ -35,54 +52,139 @@ struct Gate<'a> {
 }
 
 #[derive(Debug, Clone)]
-struct Input<'a> {
+pub struct Input<'a> {
     initial_values: HashMap<&'a str, bool>,
     gates: Vec<Gate<'a>>,
     output_gate_map: HashMap<&'a str, Gate<'a>>,
 }
 
-fn parse_input<'a>(input: &'a str) -> Input<'a> {
-    let (initial_values_raw, gates_raw) = input.split_once("\n\n").unwrap();
+fn parse_input<'a>(input: &'a str) -> Result<Input<'a>, ParseError> {
+    let (initial_values_raw, gates_raw) = parse::split_once(input, input, "\n\n")?;
 
-    let initial_values = initial_values_raw
+    let initial_values: HashMap<_, _> = initial_values_raw
         .trim()
         .lines()
         .map(|line| {
-            let (wire, val) = line.split_once(": ").unwrap();
+            let (wire, val) = parse::split_once(input, line, ": ")?;
 
-            (wire, val.trim() == "1")
+            match val.trim() {
+                "0" => Ok((wire, false)),
+                "1" => Ok((wire, true)),
+                _ => Err(ParseError::at(input, val, "`0` or `1`")),
+            }
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     let gates: Vec<Gate<'_>> = gates_raw
         .trim()
         .lines()
         .map(|line| {
-            let (inputs, output) = line.split_once("->").unwrap();
-            let mut input_parts = inputs.trim().split(" ");
-            let left = input_parts.next().unwrap();
-            let gate = input_parts.next().unwrap().parse::<GateType>().unwrap();
-            let right = input_parts.next().unwrap();
+            let (inputs, output) = parse::split_once(input, line, "->")?;
+            let inputs = inputs.trim();
+            let mut input_parts = inputs.split(" ");
+            let left = parse::next(input, inputs, &mut input_parts, "a wire")?;
+            let gate = parse::next(input, inputs, &mut input_parts, "a gate")?;
+            let gate = gate
+                .parse::<GateType>()
+                .map_err(|e| ParseError::at(input, gate, e.expected))?;
+            let right = parse::next(input, inputs, &mut input_parts, "a wire")?;
+
+            let output = output.trim();
+            if output.starts_with('z') && output_bit(output).is_none() {
+                return Err(ParseError::at(
+                    input,
+                    output,
+                    "a `z` wire numbered like `z00`",
+                ));
+            }
 
-            Gate {
+            Ok(Gate {
                 left,
                 right,
-                output: output.trim(),
+                output,
                 gate,
-            }
+            })
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     let mut output_gate_map = HashMap::new();
     for gate in &gates {
         output_gate_map.insert(gate.output, *gate);
     }
 
-    Input {
+    for gate in &gates {
+        for wire in [gate.left, gate.right] {
+            if !initial_values.contains_key(wire) && !output_gate_map.contains_key(wire) {
+                return Err(ParseError::at(
+                    input,
+                    wire,
+                    "a wire with an initial value or a gate output",
+                ));
+            }
+        }
+    }
+
+    if let Some(wire) = find_cycle(&gates, &initial_values, &output_gate_map) {
+        return Err(ParseError::at(
+            input,
+            wire,
+            "a gate whose inputs don't depend on its own output",
+        ));
+    }
+
+    Ok(Input {
         initial_values,
         gates,
         output_gate_map,
+    })
+}
+
+/// The bit of the output number that a `z` wire holds.
+fn output_bit(wire: &str) -> Option<usize> {
+    wire.strip_prefix('z')?.parse().ok()
+}
+
+/// Finds the output of a gate whose value depends on itself, which would leave
+/// `compute_value` recursing forever.
+fn find_cycle<'a>(
+    gates: &[Gate<'a>],
+    initial_values: &HashMap<&'a str, bool>,
+    output_gate_map: &HashMap<&'a str, Gate<'a>>,
+) -> Option<&'a str> {
+    fn visit<'a>(
+        wire: &'a str,
+        initial_values: &HashMap<&'a str, bool>,
+        output_gate_map: &HashMap<&'a str, Gate<'a>>,
+        visiting: &mut HashSet<&'a str>,
+        done: &mut HashSet<&'a str>,
+    ) -> Option<&'a str> {
+        if initial_values.contains_key(wire) || done.contains(wire) {
+            return None;
+        }
+        let gate = output_gate_map.get(wire)?;
+        if !visiting.insert(wire) {
+            return Some(gate.output);
+        }
+
+        let cycle = visit(gate.left, initial_values, output_gate_map, visiting, done)
+            .or_else(|| visit(gate.right, initial_values, output_gate_map, visiting, done));
+
+        visiting.remove(wire);
+        done.insert(wire);
+        cycle
     }
+
+    let mut visiting = HashSet::new();
+    let mut done = HashSet::new();
+    gates.iter().find_map(|gate| {
+        visit(
+            gate.output,
+            initial_values,
+            output_gate_map,
+            &mut visiting,
+            &mut done,
+        )
+    })
 }
 
 fn simulate(input: &Input) -> usize {
@@ -91,16 +193,15 @@ fn simulate(input: &Input) -> usize {
     let mut needed_values: Vec<_> = input
         .gates
         .iter()
-        .filter(|gate| gate.output.starts_with('z'))
-        .map(|gate| {
-            (
-                gate.output,
+        .filter_map(|gate| {
+            Some((
+                output_bit(gate.output)?,
                 compute_value(gate.output, &input.output_gate_map, &mut values),
-            )
+            ))
         })
         .collect();
 
-    needed_values.sort_by_key(|(a, _)| -1 * a.replace('z', "").parse::<isize>().unwrap());
+    needed_values.sort_by_key(|&(bit, _)| Reverse(bit));
 
     let mut res = 0;
     for (_, v) in needed_values {
@@ -119,14 +220,16 @@ fn compute_value<'a>(
         return *values.get(wire).unwrap();
     }
 
-    let gate = output_gates.get(wire).unwrap();
+    // Parsing checked that every wire without an initial value is a gate's
+    // output.
+    let gate = &output_gates[wire];
 
     let lval = compute_value(gate.left, output_gates, values);
     let rval = compute_value(gate.right, output_gates, values);
     let output = match gate.gate {
-        GateType::AND => lval && rval,
-        GateType::OR => lval || rval,
-        GateType::XOR => lval ^ rval,
+        GateType::And => lval && rval,
+        GateType::Or => lval || rval,
+        GateType::Xor => lval ^ rval,
     };
 
     values.insert(wire, output);
@@ -168,9 +271,9 @@ impl<'a> Input<'a> {
         } in self.gates.iter()
         {
             let (op, color) = match gate {
-                GateType::AND => ("&", "red"),
-                GateType::OR => ("|", "blue"),
-                GateType::XOR => ("^", "green"),
+                GateType::And => ("&", "red"),
+                GateType::Or => ("|", "blue"),
+                GateType::Xor => ("^", "green"),
             };
             let gate_node = format!("\"{left} {op} {right}\"");
             dotfile.push_str(format!("{gate_node} [color={color} fontcolor={color}]\n").as_str());
@@ -193,7 +296,7 @@ impl<'a> Input<'a> {
             for y in 0..45usize {
                 self.set_y(1 << y);
 
-                let z = simulate(&self);
+                let z = simulate(self);
                 if z != (1 << x) + (1 << y) {
                     wrong.push((1 << x, 1 << y, z));
                 }
@@ -207,10 +310,137 @@ impl<'a> Input<'a> {
             if wrong % 2 == 1 {
                 wrong_bits.push(i)
             }
-            wrong = wrong / 2;
+            wrong /= 2;
             i += 1
         }
 
         wrong_bits
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_EXAMPLE: &str = "\
+x00: 1
+x01: 1
+x02: 1
+y00: 0
+y01: 1
+y02: 0
+
+x00 AND y00 -> z00
+x01 XOR y01 -> z01
+x02 OR y02 -> z02
+";
+
+    const LARGE_EXAMPLE: &str = "\
+x00: 1
+x01: 0
+x02: 1
+x03: 1
+x04: 0
+y00: 1
+y01: 1
+y02: 1
+y03: 1
+y04: 1
+
+ntg XOR fgs -> mjb
+y02 OR x01 -> tnw
+kwq OR kpj -> z05
+x00 OR x03 -> fst
+tgd XOR rvg -> z01
+vdt OR tnw -> bfw
+bfw AND frj -> z10
+ffh OR nrd -> bqk
+y00 AND y03 -> djm
+y03 OR y00 -> psh
+bqk OR frj -> z08
+tnw OR fst -> frj
+gnj AND tgd -> z11
+bfw XOR mjb -> z00
+x03 OR x00 -> vdt
+gnj AND wpb -> z02
+x04 AND y00 -> kjc
+djm OR pbm -> qhw
+nrd AND vdt -> hwm
+kjc AND fst -> rvg
+y04 OR y02 -> fgs
+y01 AND x02 -> pbm
+ntg OR kjc -> kwq
+psh XOR fgs -> tgd
+qhw XOR tgd -> z09
+pbm OR djm -> kpj
+x03 XOR y03 -> ffh
+x00 XOR y04 -> ntg
+bfw OR bqk -> z06
+nrd XOR fgs -> wpb
+frj XOR qhw -> z04
+bqk OR frj -> z07
+y03 OR x01 -> nrd
+hwm AND bqk -> z03
+tgd XOR rvg -> z12
+tnw OR pbm -> gnj
+";
+
+    #[test]
+    fn parses_gates() {
+        let input = parse_input(SMALL_EXAMPLE).unwrap();
+
+        assert_eq!(input.initial_values.len(), 6);
+        assert!(input.initial_values["x00"]);
+        assert!(!input.initial_values["y00"]);
+        assert_eq!(input.gates.len(), 3);
+        assert!(matches!(input.output_gate_map["z01"].gate, GateType::Xor));
+    }
+
+    #[test]
+    fn simulates_examples() {
+        assert_eq!(simulate(&parse_input(SMALL_EXAMPLE).unwrap()), 4);
+        assert_eq!(simulate(&parse_input(LARGE_EXAMPLE).unwrap()), 2024);
+    }
+
+    #[test]
+    fn rejects_unknown_gates() {
+        let error = parse_input("x00: 1\ny00: 0\n\nx00 NAND y00 -> z00\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (4, 5));
+        assert_eq!(error.found, "`NAND`");
+    }
+
+    #[test]
+    fn rejects_undefined_wires() {
+        let error = parse_input("x00: 1\ny00: 0\n\nx00 AND y01 -> z00\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (4, 9));
+        assert_eq!(error.found, "`y01`");
+    }
+
+    #[test]
+    fn rejects_unnumbered_outputs() {
+        let error = parse_input("x00: 1\ny00: 0\n\nx00 AND y00 -> zed\n").unwrap_err();
+
+        assert_eq!((error.line, error.column), (4, 16));
+        assert_eq!(error.found, "`zed`");
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        let error = parse_input(
+            "x00: 1\ny00: 0\n\nx00 AND abc -> def\ndef OR y00 -> abc\nabc XOR y00 -> z00\n",
+        )
+        .unwrap_err();
+
+        assert_eq!((error.line, error.column), (4, 16));
+        assert_eq!(error.found, "`def`");
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day24::parse(LARGE_EXAMPLE).unwrap();
+
+        assert_eq!(Day24::part1(&input), Answer::Number(2024));
+    }
+}
@@ -1,8 +1,21 @@
-const INPUT: &'static str = include_str!("./input.in");
+use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
 
-pub fn run() {
-    let input = parse_input(INPUT);
-    println!("Part 1: {}", count_potential_matches(&input));
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: usize = 25;
+    type Input<'a> = Input;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Answer {
+        count_potential_matches(input).into()
+    }
 }
 
 fn count_potential_matches(input: &Input) -> usize {
@@ -25,25 +38,37 @@ enum SchematicType {
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     keys: Vec<[u8; 5]>,
     locks: Vec<[u8; 5]>,
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input, ParseError> {
     let mut keys = vec![];
     let mut locks = vec![];
     for schematic_raw in input.split("\n\n") {
-        let first_ch = schematic_raw.chars().next().unwrap();
-        let st = if first_ch == '.' {
-            SchematicType::Key
-        } else {
-            SchematicType::Lock
+        let st = match schematic_raw.chars().next() {
+            Some('.') => SchematicType::Key,
+            Some('#') => SchematicType::Lock,
+            _ => return Err(ParseError::at(input, schematic_raw, "a schematic")),
         };
+        let first_ch = if st == SchematicType::Key { '.' } else { '#' };
 
         let mut heights = [0u8; 5];
         for (i, line) in schematic_raw.trim().lines().enumerate() {
-            for (j, c) in line.trim().chars().enumerate() {
+            let line = line.trim();
+            for (j, (k, c)) in line.char_indices().enumerate() {
+                if j >= heights.len() {
+                    return Err(ParseError::at(input, &line[k..], "end of line"));
+                }
+                if c != '#' && c != '.' {
+                    return Err(ParseError::at(
+                        input,
+                        &line[k..k + c.len_utf8()],
+                        "`#` or `.`",
+                    ));
+                }
+
                 if heights[j] == 0 && c != first_ch {
                     heights[j] = if st == SchematicType::Key {
                         6 - i as u8
@@ -61,5 +86,72 @@ fn parse_input(input: &str) -> Input {
         }
     }
 
-    Input { keys, locks }
+    Ok(Input { keys, locks })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####
+";
+
+    #[test]
+    fn parses_heights() {
+        let input = parse_input(EXAMPLE).unwrap();
+
+        // Lock heights include the top row, so they fit a key when the sum is
+        // at most 6.
+        assert_eq!(input.locks, [[1, 6, 4, 5, 4], [2, 3, 1, 6, 4]]);
+        assert_eq!(
+            input.keys,
+            [[5, 0, 2, 1, 3], [4, 3, 4, 0, 2], [3, 0, 2, 0, 1]]
+        );
+    }
+
+    #[test]
+    fn solves_example() {
+        let input = Day25::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day25::part1(&input), Answer::Number(3));
+    }
 }
@@ -0,0 +1,63 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::DEFAULT_YEAR;
+
+/// Where a day's puzzle input is read from.
+pub enum InputSource {
+    /// `input.in` in the day's directory; see [`day_dir`].
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn describe(&self, year: usize, day: usize) -> String {
+        match self {
+            InputSource::Default => default_input_path(year, day).display().to_string(),
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => "<stdin>".to_string(),
+        }
+    }
+
+    /// The file the input is read from, if it comes from one.
+    pub fn path(&self, year: usize, day: usize) -> Option<PathBuf> {
+        match self {
+            InputSource::Default => Some(default_input_path(year, day)),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    pub fn read(&self, year: usize, day: usize) -> io::Result<String> {
+        match self {
+            InputSource::Default => fs::read_to_string(default_input_path(year, day)),
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+pub fn default_input_path(year: usize, day: usize) -> PathBuf {
+    day_dir(year, day).join("input.in")
+}
+
+/// The directory holding a day's module and input: `src/dayNN` for 2024, and
+/// `src/yearYYYY/dayNN` for any other year.
+pub fn day_dir(year: usize, day: usize) -> PathBuf {
+    let src = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+    let parent = if year == DEFAULT_YEAR {
+        src
+    } else {
+        src.join(format!("year{}", year))
+    };
+
+    parent.join(format!("day{:02}", day))
+}
@@ -0,0 +1,53 @@
+//! Solutions for Advent of Code 2024, and the harness that runs them.
+//!
+//! Each day lives in its own module as a type implementing
+//! [`Solution`](solution::Solution), so it can be parsed and solved directly:
+//!
+//! ```
+//! use aoc_2024::{day01::Day01, solution::{Answer, Solution}};
+//!
+//! let input = Day01::parse("3   4\n4   3\n").unwrap();
+//! assert_eq!(Day01::part1(&input), Answer::Number(0));
+//! ```
+//!
+//! [`DAYS`] registers every day so it can be run by year and number with
+//! timings and panics caught, which is what the `aoc-2024` binary does. It is
+//! generated by the build script from the `dayNN` directories under `src` (and
+//! `src/yearYYYY` for years other than 2024).
+
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod input;
+pub mod parse;
+pub mod runner;
+pub mod solution;
+pub mod submit;
+pub mod util;
+
+use runner::Day;
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+/// The year whose days are kept directly under `src`, rather than in a
+/// `src/yearYYYY` directory.
+pub const DEFAULT_YEAR: usize = 2024;
+
+/// Looks up a day in the registry by its year and number.
+pub fn day(year: usize, n: usize) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.year == year && day.number == n)
+}
+
+/// The registered days for `year`, in order.
+pub fn days(year: usize) -> impl Iterator<Item = &'static Day> {
+    DAYS.iter().filter(move |day| day.year == year)
+}
+
+/// The most recent year with any registered days.
+pub fn latest_year() -> usize {
+    DAYS.iter()
+        .map(|day| day.year)
+        .max()
+        .unwrap_or(DEFAULT_YEAR)
+}
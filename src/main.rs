@@ -1,76 +1,683 @@
-use std::{env::args, time::Instant};
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod util;
-
-fn run_day(n: usize) {
+use std::{
+    env::args,
+    path::{Path, PathBuf},
+    process::exit,
+    time::{Duration, Instant},
+};
+
+mod output;
+mod scaffold;
+mod table;
+mod watch;
+
+use aoc_2024::{
+    answers::{answers_path, Answers, Verdict},
+    bench::{self, bench_baseline_path, bench_results_path, BenchResults},
+    client::{config_path, Client, Config, Fetched},
+    input::InputSource,
+    input::{day_dir, default_input_path},
+    runner::{self, Day, Report},
+    submit::{history_path, History},
+};
+use output::{DayOutput, Format};
+use table::{format_bytes, format_duration, Table};
+
+fn prepare(year: usize, n: usize, source: &InputSource) -> Result<(&'static Day, String), String> {
+    let day = aoc_2024::day(year, n).ok_or_else(|| "not yet implemented".to_string())?;
+
+    let input = source.read(year, n).map_err(|e| {
+        format!(
+            "could not read input from {}: {}",
+            source.describe(year, n),
+            e
+        )
+    })?;
+
+    Ok((day, input))
+}
+
+fn execute(year: usize, n: usize, source: &InputSource) -> Result<Report, String> {
+    let (day, input) = prepare(year, n, source)?;
+
+    day.run(&input)
+}
+
+/// Runs one day, printing its answers and timings. Returns whether it ran.
+fn run_day(year: usize, n: usize, source: &InputSource) -> bool {
+    runner::capture_panics();
+
     println!("Day {}", n);
+
+    match execute(year, n, source) {
+        Ok(report) => {
+            println!("Part 1: {}", report.part1);
+            println!("Part 2: {}", report.part2);
+
+            let timings = report.timings;
+            println!(
+                "Took {} (parse {}, part 1 {}, part 2 {})",
+                format_duration(timings.total()),
+                format_duration(timings.parse),
+                format_duration(timings.part1),
+                format_duration(timings.part2)
+            );
+
+            if let Some(allocations) = report.allocations {
+                let stages = [
+                    ("parse", allocations.parse),
+                    ("part 1", allocations.part1),
+                    ("part 2", allocations.part2),
+                ];
+                println!(
+                    "Heap (peak, total, allocations): {}",
+                    stages
+                        .iter()
+                        .map(|(stage, stats)| format!(
+                            "{} {}, {}, {}",
+                            stage,
+                            format_bytes(stats.peak_bytes),
+                            format_bytes(stats.total_bytes),
+                            stats.count
+                        ))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                );
+            }
+
+            true
+        }
+        Err(e) => {
+            eprintln!("Day {} failed: {}", n, e);
+            false
+        }
+    }
+}
+
+/// Prints each day's result as a line of JSON. Returns whether every day ran.
+fn run_days_json(year: usize, days: &[usize], source: &InputSource, jobs: usize) -> bool {
+    runner::capture_panics();
+
+    let reports = runner::parallel_map(days, jobs, |&n| execute(year, n, source));
+    let mut all_ran = true;
+    for (&n, report) in days.iter().zip(reports) {
+        all_ran &= report.is_ok();
+        let output = DayOutput::new(year, n, source.describe(year, n), report);
+        println!("{}", output.to_json());
+    }
+
+    all_ran
+}
+
+/// Prints a table of every day's results. Returns whether every day ran.
+fn run_days(year: usize, days: &[usize], jobs: usize) -> bool {
+    runner::capture_panics();
+
+    let mut table = Table::new(&[
+        "Day",
+        "Part 1",
+        "Part 2",
+        "Parse",
+        "Part 1 time",
+        "Part 2 time",
+        "Total",
+    ]);
+    let mut total = Duration::ZERO;
+
     let start = Instant::now();
+    let reports = runner::parallel_map(days, jobs, |&n| execute(year, n, &InputSource::Default));
+    let elapsed = start.elapsed();
 
-    match n {
-        1 => day01::run(),
-        2 => day02::run(),
-        3 => day03::run(),
-        4 => day04::run(),
-        5 => day05::run(),
-        6 => day06::run(),
-        7 => day07::run(),
-        8 => day08::run(),
-        9 => day09::run(),
-        10 => day10::run(),
-        11 => day11::run(),
-        12 => day12::run(),
-        13 => day13::run(),
-        14 => day14::run(),
-        15 => day15::run(),
-        16 => day16::run(),
-        17 => day17::run(),
-        18 => day18::run(),
-        19 => day19::run(),
-        20 => day20::run(),
-        21 => day21::run(),
-        22 => day22::run(),
-        23 => day23::run(),
-        24 => day24::run(),
-        25 => day25::run(),
-        _ => panic!("Not yet implemented: day {}", n),
-    }
-
-    let duration = Instant::now() - start;
-    println!("Took {}ms", duration.as_millis());
+    let mut all_ran = true;
+    for (&n, report) in days.iter().zip(reports) {
+        match report {
+            Ok(report) => {
+                let timings = report.timings;
+                total += timings.total();
+
+                table.row(vec![
+                    n.to_string(),
+                    report.part1.to_string(),
+                    report.part2.to_string(),
+                    format_duration(timings.parse),
+                    format_duration(timings.part1),
+                    format_duration(timings.part2),
+                    format_duration(timings.total()),
+                ]);
+            }
+            Err(e) => {
+                table.row(vec![n.to_string(), format!("failed: {}", e)]);
+                all_ran = false;
+            }
+        }
+    }
+
+    print!("{}", table);
+    if jobs > 1 {
+        println!(
+            "Total time: {} ({} wall clock)",
+            format_duration(total),
+            format_duration(elapsed)
+        );
+    } else {
+        println!("Total time: {}", format_duration(total));
+    }
+
+    all_ran
 }
 
-const LATEST_DAY: usize = 25;
+/// Parses a selection of days: `all`, a single day, a range like `1..=10` or
+/// `1..11`, or a comma-separated list of any of these.
+fn parse_selection(year: usize, selection: &str) -> Option<Vec<usize>> {
+    if selection == "all" {
+        return Some(aoc_2024::days(year).map(|day| day.number).collect());
+    }
 
-fn main() {
-    run_day(
-        args()
-            .nth(1)
-            .and_then(|s| s.parse::<usize>().ok())
-            .unwrap_or(LATEST_DAY),
+    let mut days = vec![];
+    for part in selection.split(',') {
+        if let Some((start, end)) = part.split_once("..=") {
+            days.extend(start.parse::<usize>().ok()?..=end.parse().ok()?);
+        } else if let Some((start, end)) = part.split_once("..") {
+            days.extend(start.parse::<usize>().ok()?..end.parse().ok()?);
+        } else {
+            days.push(part.parse().ok()?);
+        }
+    }
+
+    if days.is_empty() {
+        None
+    } else {
+        Some(days)
+    }
+}
+
+/// The latest registered day, which is what runs when no day is given.
+fn latest_day(year: usize) -> Vec<usize> {
+    aoc_2024::days(year)
+        .last()
+        .map(|day| day.number)
+        .into_iter()
+        .collect()
+}
+
+/// Checks each day's answers against the `answers.toml` next to its input,
+/// or with `record` set, saves the current answers there instead. Returns
+/// whether every day ran and matched.
+fn verify_days(year: usize, days: &[usize], source: &InputSource, record: bool) -> bool {
+    runner::capture_panics();
+
+    let mut table = Table::new(&["Day", "Part 1", "Part 2"]);
+    let (mut passed, mut failed, mut missing, mut errors) = (0, 0, 0, 0);
+
+    for &n in days {
+        let Some(path) = source.path(year, n).map(|path| answers_path(&path)) else {
+            table.row(vec![
+                n.to_string(),
+                "failed: no answers file for stdin".to_string(),
+            ]);
+            errors += 1;
+            continue;
+        };
+
+        let report = match execute(year, n, source) {
+            Ok(report) => report,
+            Err(e) => {
+                table.row(vec![n.to_string(), format!("failed: {}", e)]);
+                errors += 1;
+                continue;
+            }
+        };
+
+        let outcome = if record {
+            Answers::from_report(&report)
+                .save(&path)
+                .map(|_| [report.part1.to_string(), report.part2.to_string()])
+        } else {
+            Answers::load(&path).map(|answers| {
+                answers.verify(&report).map(|verdict| {
+                    match verdict {
+                        Verdict::Pass => passed += 1,
+                        Verdict::Fail { .. } => failed += 1,
+                        Verdict::Missing => missing += 1,
+                        Verdict::Unsolved => {}
+                    }
+
+                    verdict.to_string()
+                })
+            })
+        };
+
+        match outcome {
+            Ok([part1, part2]) => table.row(vec![n.to_string(), part1, part2]),
+            Err(e) => {
+                table.row(vec![
+                    n.to_string(),
+                    format!("failed: {}: {}", path.display(), e),
+                ]);
+                errors += 1;
+            }
+        }
+    }
+
+    print!("{}", table);
+    if record {
+        println!("Recorded answers for {} days", days.len() - errors);
+    } else {
+        println!(
+            "{} passed, {} failed, {} missing, {} errors",
+            passed, failed, missing, errors
+        );
+    }
+
+    failed == 0 && errors == 0
+}
+
+/// Benchmarks each day, comparing median times against the baseline and
+/// flagging any stage that got slower by more than `threshold` percent. With
+/// `save_baseline` set, this run becomes the new baseline for its days.
+/// Returns whether every day ran without regressing.
+fn bench_days(
+    year: usize,
+    days: &[usize],
+    source: &InputSource,
+    iterations: usize,
+    threshold: f64,
+    save_baseline: bool,
+) -> bool {
+    runner::capture_panics();
+
+    let load = |path: &Path| {
+        BenchResults::load(path).unwrap_or_else(|e| {
+            eprintln!("Ignoring previous results in {}: {}", path.display(), e);
+            BenchResults::default()
+        })
+    };
+    let (results_path, baseline_path) = (bench_results_path(), bench_baseline_path());
+    let mut results = load(&results_path);
+    let mut baseline = load(&baseline_path);
+
+    let mut table = Table::new(&[
+        "Day", "Stage", "Samples", "Min", "Median", "p95", "Mean", "Change",
+    ]);
+    let mut regressions = 0;
+    let mut failed = 0;
+
+    for &n in days {
+        let report =
+            match prepare(year, n, source).and_then(|(day, input)| day.bench(&input, iterations)) {
+                Ok(report) => report,
+                Err(e) => {
+                    table.row(vec![n.to_string(), format!("failed: {}", e)]);
+                    failed += 1;
+                    continue;
+                }
+            };
+
+        let previous = baseline.get(year, n).map(|previous| previous.stages());
+        for (i, (stage, stats)) in report.stages().into_iter().enumerate() {
+            let change = match previous {
+                Some(previous) => {
+                    let change = bench::change(previous[i].1, stats);
+                    if change > threshold {
+                        regressions += 1;
+                        format!("{:+.1}% REGRESSION", change)
+                    } else {
+                        format!("{:+.1}%", change)
+                    }
+                }
+                None => "-".to_string(),
+            };
+
+            table.row(vec![
+                n.to_string(),
+                stage.to_string(),
+                stats.samples.to_string(),
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.p95),
+                format_duration(stats.mean),
+                change,
+            ]);
+        }
+
+        if save_baseline {
+            baseline.insert(year, n, report);
+        }
+        results.insert(year, n, report);
+    }
+
+    print!("{}", table);
+    println!(
+        "{} regressions above {}% in median time",
+        regressions, threshold
     );
+
+    if let Err(e) = results.save(&results_path) {
+        eprintln!(
+            "Could not save results to {}: {}",
+            results_path.display(),
+            e
+        );
+    }
+    if save_baseline {
+        match baseline.save(&baseline_path) {
+            Ok(()) => println!("Saved as the baseline in {}", baseline_path.display()),
+            Err(e) => eprintln!(
+                "Could not save the baseline to {}: {}",
+                baseline_path.display(),
+                e
+            ),
+        }
+    }
+
+    regressions == 0 && failed == 0
+}
+
+/// Downloads each day's input to where it will be read from, skipping days
+/// that already have one.
+fn fetch_days(year: usize, days: &[usize], source: &InputSource) -> bool {
+    let path = config_path();
+    let client = match Config::load(&path)
+        .map_err(|e| format!("could not read {}: {}", path.display(), e))
+        .and_then(|config| Client::new(config).map_err(|e| e.to_string()))
+    {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    for &n in days {
+        let Some(path) = source.path(year, n) else {
+            eprintln!("Day {}: can't fetch into stdin", n);
+            return false;
+        };
+
+        match client.fetch_input(year, n, &path) {
+            Ok(Fetched::Cached) => println!("Day {}: already have {}", n, path.display()),
+            Ok(Fetched::Downloaded) => println!("Day {}: saved {}", n, path.display()),
+            Err(e) => {
+                eprintln!("Day {}: {}", n, e);
+                ok = false;
+            }
+        }
+    }
+
+    ok
+}
+
+/// Runs a day and submits its answer to `part`, unless earlier attempts show
+/// it must be wrong, recording the outcome in the day's history.
+fn submit_day(year: usize, n: usize, part: usize, source: &InputSource) -> Result<(), String> {
+    let report = execute(year, n, source)?;
+    let answer = match part {
+        1 => &report.part1,
+        _ => &report.part2,
+    };
+    let answer = answer
+        .solved()
+        .ok_or_else(|| format!("part {} is unsolved", part))?;
+
+    let path = history_path(
+        &source
+            .path(year, n)
+            .unwrap_or_else(|| default_input_path(year, n)),
+    );
+    let mut history =
+        History::load(&path).map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    history
+        .check(part, &answer)
+        .map_err(|refusal| format!("not submitting {}: {}", answer, refusal))?;
+
+    let config_path = config_path();
+    let client = Config::load(&config_path)
+        .map_err(|e| format!("could not read {}: {}", config_path.display(), e))
+        .and_then(|config| Client::new(config).map_err(|e| e.to_string()))?;
+    let outcome = client
+        .submit(year, n, part, &answer)
+        .map_err(|e| e.to_string())?;
+
+    history.record(part, &answer, outcome);
+    println!("Day {} part {}: {} is {}", n, part, answer, outcome);
+
+    history
+        .save(&path)
+        .map_err(|e| format!("could not save {}: {}", path.display(), e))
+}
+
+const DEFAULT_ITERATIONS: usize = 100;
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+enum Command {
+    Run {
+        format: Format,
+        jobs: usize,
+    },
+    Verify {
+        record: bool,
+    },
+    Bench {
+        iterations: usize,
+        threshold: f64,
+        save_baseline: bool,
+    },
+    New,
+    Fetch,
+    Submit {
+        part: Option<usize>,
+    },
+    Watch,
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: aoc-2024 [day|all|<from>..=<to>|<day>,<day>,...] [--input <path>|-] [--format text|json] [--jobs <n>]"
+    );
+    eprintln!("       aoc-2024 verify [days] [--record]");
+    eprintln!(
+        "       aoc-2024 bench [days] [--iterations <n>] [--threshold <percent>] [--save-baseline]"
+    );
+    eprintln!("       aoc-2024 new <day> [--year <year>]");
+    eprintln!("       aoc-2024 fetch <days> [--input <path>]");
+    eprintln!("       aoc-2024 submit <day> <part> [--input <path>|-]");
+    eprintln!("       aoc-2024 watch <day> [--input <path>]");
+    eprintln!(
+        "Every command also takes --year <year>, defaulting to the latest year with any days."
+    );
+    exit(1)
+}
+
+fn main() {
+    let mut args = args().skip(1).peekable();
+
+    let mut command = match args.peek().map(|arg| arg.as_str()) {
+        Some("verify") => {
+            args.next();
+            Command::Verify { record: false }
+        }
+        Some("bench") => {
+            args.next();
+            Command::Bench {
+                iterations: DEFAULT_ITERATIONS,
+                threshold: DEFAULT_THRESHOLD,
+                save_baseline: false,
+            }
+        }
+        Some("new") => {
+            args.next();
+            Command::New
+        }
+        Some("fetch") => {
+            args.next();
+            Command::Fetch
+        }
+        Some("watch") => {
+            args.next();
+            Command::Watch
+        }
+        Some("submit") => {
+            args.next();
+            Command::Submit { part: None }
+        }
+        _ => Command::Run {
+            format: Format::Text,
+            jobs: 1,
+        },
+    };
+
+    let mut selection = None;
+    let mut source = InputSource::Default;
+    let mut year = aoc_2024::latest_year();
+
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), &mut command) {
+            ("--input" | "-i", _) => {
+                source = match args.next().as_deref() {
+                    Some("-") => InputSource::Stdin,
+                    Some(path) => InputSource::File(PathBuf::from(path)),
+                    None => usage(),
+                }
+            }
+            ("--year", _) => {
+                year = args
+                    .next()
+                    .and_then(|y| y.parse().ok())
+                    .unwrap_or_else(|| usage())
+            }
+            ("--format", Command::Run { format, .. }) => {
+                *format = args
+                    .next()
+                    .and_then(|f| Format::parse(&f))
+                    .unwrap_or_else(|| usage())
+            }
+            ("--jobs" | "-j", Command::Run { jobs, .. }) => {
+                *jobs = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|&n| n > 0)
+                    .unwrap_or_else(|| usage())
+            }
+            ("--record", Command::Verify { record }) => *record = true,
+            ("--save-baseline", Command::Bench { save_baseline, .. }) => *save_baseline = true,
+            ("--iterations", Command::Bench { iterations, .. }) => {
+                *iterations = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|&n| n > 0)
+                    .unwrap_or_else(|| usage())
+            }
+            ("--threshold", Command::Bench { threshold, .. }) => {
+                *threshold = args
+                    .next()
+                    .and_then(|t| t.parse().ok())
+                    .unwrap_or_else(|| usage())
+            }
+            (_, Command::Submit { part }) if selection.is_some() && part.is_none() => {
+                *part = Some(
+                    arg.parse()
+                        .ok()
+                        .filter(|part| (1..=2).contains(part))
+                        .unwrap_or_else(|| usage()),
+                )
+            }
+            _ => selection = Some(arg),
+        }
+    }
+
+    let mut days = selection.map(|s| parse_selection(year, &s).unwrap_or_else(|| usage()));
+
+    if !matches!(source, InputSource::Default) {
+        // An input with no day given is for the latest day, as with `run`.
+        let days = days.get_or_insert_with(|| latest_day(year));
+        if days.len() != 1 {
+            eprintln!("--input can only be used when running a single day");
+            exit(1)
+        }
+    }
+
+    match command {
+        Command::Run { format, jobs } => {
+            let days = days.unwrap_or_else(|| latest_day(year));
+            let all_ran = match (format, days.as_slice()) {
+                (Format::Json, days) => run_days_json(year, days, &source, jobs),
+                (Format::Text, [day]) => run_day(year, *day, &source),
+                (Format::Text, days) => run_days(year, days, jobs),
+            };
+            if !all_ran {
+                exit(1)
+            }
+        }
+        Command::Verify { record } => {
+            let days = days.unwrap_or_else(|| aoc_2024::days(year).map(|day| day.number).collect());
+            if !verify_days(year, &days, &source, record) {
+                exit(1)
+            }
+        }
+        Command::Bench {
+            iterations,
+            threshold,
+            save_baseline,
+        } => {
+            let days = days.unwrap_or_else(|| aoc_2024::days(year).map(|day| day.number).collect());
+            if !bench_days(year, &days, &source, iterations, threshold, save_baseline) {
+                exit(1)
+            }
+        }
+        Command::New => {
+            let Some([day]) = days.as_deref() else {
+                usage()
+            };
+            if !(1..=25).contains(day) {
+                eprintln!("Advent of Code days run from 1 to 25");
+                exit(1)
+            }
+
+            match scaffold::new_day(year, *day) {
+                Ok(module) => println!(
+                    "Created {} and an empty input.in; it is registered on the next build",
+                    module.display()
+                ),
+                Err(e) => {
+                    eprintln!("Could not create day {}: {}", day, e);
+                    exit(1)
+                }
+            }
+        }
+        Command::Submit { part } => {
+            let (Some([day]), Some(part)) = (days.as_deref(), part) else {
+                usage()
+            };
+            if let Err(e) = submit_day(year, *day, part, &source) {
+                eprintln!("Day {} part {}: {}", day, part, e);
+                exit(1)
+            }
+        }
+        Command::Watch => {
+            let Some([day]) = days.as_deref() else {
+                usage()
+            };
+
+            let mut paths = vec![day_dir(year, *day)];
+            let input = match source {
+                InputSource::Default => None,
+                InputSource::File(path) => {
+                    paths.push(path.clone());
+                    Some(path)
+                }
+                InputSource::Stdin => {
+                    eprintln!("Can't watch stdin for changes");
+                    exit(1)
+                }
+            };
+
+            watch::watch(year, *day, paths, input)
+        }
+        Command::Fetch => {
+            let days = days.unwrap_or_else(|| usage());
+            if !fetch_days(year, &days, &source) {
+                exit(1)
+            }
+        }
+    }
 }
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+use aoc_2024::runner::{Report, Timings};
+
+/// How a run's results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    /// One JSON object per day, each on its own line.
+    Json,
+}
+
+impl Format {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+/// The result of running one day, in the shape printed by `--format json`.
+/// Every field is always present, with `null` standing in for answers and
+/// timings that a failed day doesn't have.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DayOutput {
+    pub year: usize,
+    pub day: usize,
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub timings: Option<TimingsOutput>,
+    pub error: Option<String>,
+}
+
+/// Timings in nanoseconds.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimingsOutput {
+    pub parse_ns: u64,
+    pub part1_ns: u64,
+    pub part2_ns: u64,
+    pub total_ns: u64,
+}
+
+impl From<Timings> for TimingsOutput {
+    fn from(timings: Timings) -> Self {
+        TimingsOutput {
+            parse_ns: timings.parse.as_nanos() as u64,
+            part1_ns: timings.part1.as_nanos() as u64,
+            part2_ns: timings.part2.as_nanos() as u64,
+            total_ns: timings.total().as_nanos() as u64,
+        }
+    }
+}
+
+impl DayOutput {
+    pub fn new(year: usize, day: usize, input: String, result: Result<Report, String>) -> Self {
+        match result {
+            Ok(report) => DayOutput {
+                year,
+                day,
+                input,
+                part1: report.part1.solved(),
+                part2: report.part2.solved(),
+                timings: Some(report.timings.into()),
+                error: None,
+            },
+            Err(e) => DayOutput {
+                year,
+                day,
+                input,
+                part1: None,
+                part2: None,
+                timings: None,
+                error: Some(e),
+            },
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("day output is always serializable")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use aoc_2024::solution::Answer;
+
+    #[test]
+    fn serializes_a_successful_day() {
+        let report = Report {
+            part1: Answer::Number(11),
+            part2: Answer::Unsolved,
+            timings: Timings {
+                parse: Duration::from_nanos(100),
+                part1: Duration::from_nanos(20),
+                part2: Duration::from_nanos(3),
+            },
+            allocations: None,
+        };
+
+        assert_eq!(
+            DayOutput::new(2024, 1, "day01.in".to_string(), Ok(report)).to_json(),
+            r#"{"year":2024,"day":1,"input":"day01.in","part1":"11","part2":null,"timings":{"parse_ns":100,"part1_ns":20,"part2_ns":3,"total_ns":123},"error":null}"#
+        );
+    }
+
+    #[test]
+    fn serializes_a_failed_day() {
+        let output = DayOutput::new(2024, 2, "<stdin>".to_string(), Err("panicked".to_string()));
+
+        assert_eq!(
+            output.to_json(),
+            r#"{"year":2024,"day":2,"input":"<stdin>","part1":null,"part2":null,"timings":null,"error":"panicked"}"#
+        );
+    }
+}
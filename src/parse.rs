@@ -0,0 +1,197 @@
+use std::{fmt::Display, str::FromStr};
+
+/// A problem with a day's puzzle input, located by line and column (both
+/// counted from 1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Filled in by the runner, which knows which day the input is for.
+    pub day: Option<usize>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    /// An error at the start of `fragment`, which must be a slice of `input`.
+    pub fn at(input: &str, fragment: &str, expected: impl Into<String>) -> Self {
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len())
+            .expect("fragment is not part of the input");
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        let token = fragment
+            .split(char::is_whitespace)
+            .next()
+            .unwrap_or_default();
+        let found = if !token.is_empty() {
+            format!("`{}`", token)
+        } else if offset == input.len() {
+            "end of input".to_string()
+        } else if input[offset..].starts_with('\n') {
+            "end of line".to_string()
+        } else {
+            format!("{:?}", input[offset..].chars().next().unwrap())
+        };
+
+        ParseError {
+            day: None,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+            found,
+        }
+    }
+
+    pub fn for_day(self, day: usize) -> Self {
+        ParseError {
+            day: Some(day),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid input")?;
+        if let Some(day) = self.day {
+            write!(f, " for day {}", day)?;
+        }
+
+        write!(
+            f,
+            " at line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `token`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, "a number"))
+}
+
+/// Splits `s`, a slice of `input`, around the first `delimiter`.
+pub fn split_once<'a>(
+    input: &str,
+    s: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(delimiter)
+        .ok_or_else(|| ParseError::at(input, s, format!("{:?}", delimiter)))
+}
+
+/// Strips `prefix` from `s`, a slice of `input`.
+pub fn strip_prefix<'a>(input: &str, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    s.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::at(input, s, format!("{:?}", prefix)))
+}
+
+/// Takes the next item from an iterator over parts of `within`, a slice of
+/// `input`, failing at the end of `within` if there are none left.
+pub fn next<'a>(
+    input: &str,
+    within: &'a str,
+    items: &mut impl Iterator<Item = &'a str>,
+    expected: &str,
+) -> Result<&'a str, ParseError> {
+    items
+        .next()
+        .ok_or_else(|| ParseError::at(input, &within[within.len()..], expected))
+}
+
+/// Parses each character of each line of `input` with `cell`, which is given
+/// the character and its `(x, y)` position and returns `None` to reject it.
+pub fn grid<T>(
+    input: &str,
+    expected: &str,
+    mut cell: impl FnMut(char, usize, usize) -> Option<T>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.char_indices()
+                .enumerate()
+                .map(|(x, (i, c))| {
+                    cell(c, x, y)
+                        .ok_or_else(|| ParseError::at(input, &line[i..i + c.len_utf8()], expected))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "12 34\nab cd\n";
+
+    #[test]
+    fn locates_fragments() {
+        let error = ParseError::at(INPUT, &INPUT[9..], "a letter");
+
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.found, "`cd`");
+    }
+
+    #[test]
+    fn describes_ends() {
+        assert_eq!(
+            ParseError::at(INPUT, &INPUT[5..5], "x").found,
+            "end of line"
+        );
+        assert_eq!(
+            ParseError::at(INPUT, &INPUT[12..], "x").found,
+            "end of input"
+        );
+        assert_eq!(ParseError::at(INPUT, &INPUT[2..2], "x").found, "' '");
+    }
+
+    #[test]
+    fn formats_with_day() {
+        let error = number::<usize>(INPUT, &INPUT[6..8]).unwrap_err().for_day(3);
+
+        assert_eq!(
+            error.to_string(),
+            "invalid input for day 3 at line 2, column 1: expected a number, found `ab`"
+        );
+    }
+
+    #[test]
+    fn parses_helpers() {
+        assert_eq!(number::<usize>(INPUT, &INPUT[3..5]), Ok(34));
+        assert_eq!(split_once(INPUT, &INPUT[..5], " "), Ok(("12", "34")));
+        assert_eq!(strip_prefix(INPUT, &INPUT[6..], "ab "), Ok("cd\n"));
+        assert_eq!(
+            next(INPUT, &INPUT[..5], &mut INPUT[..5].split(' '), "a number"),
+            Ok("12")
+        );
+        assert_eq!(
+            next(INPUT, &INPUT[..5], &mut std::iter::empty(), "a number")
+                .unwrap_err()
+                .found,
+            "end of line"
+        );
+    }
+
+    #[test]
+    fn parses_grid() {
+        let grid = grid("12\n34\n", "a digit", |c, _, _| c.to_digit(10));
+
+        assert_eq!(grid, Ok(vec![vec![1, 2], vec![3, 4]]));
+
+        let error = super::grid("12\n3x\n", "a digit", |c, _, _| c.to_digit(10)).unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.found, "`x`");
+    }
+}
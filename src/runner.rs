@@ -0,0 +1,245 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{self, catch_unwind, UnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, Once,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc::{self, AllocStats},
+    bench::{self, BenchReport},
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// Heap usage for each stage, counted with the `alloc-profile` feature.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Allocations {
+    pub parse: AllocStats,
+    pub part1: AllocStats,
+    pub part2: AllocStats,
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub part1: Answer,
+    pub part2: Answer,
+    pub timings: Timings,
+    /// `None` unless built with the `alloc-profile` feature.
+    pub allocations: Option<Allocations>,
+}
+
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+
+    (res, start.elapsed())
+}
+
+fn run<S: Solution>(input: &str) -> Result<Report, ParseError> {
+    let ((parsed, parse), parse_allocs) = alloc::measure(|| timed(|| S::parse(input)));
+    let parsed = parsed.map_err(|e| e.for_day(S::DAY))?;
+    let ((part1, part1_time), part1_allocs) = alloc::measure(|| timed(|| S::part1(&parsed)));
+    let ((part2, part2_time), part2_allocs) = alloc::measure(|| timed(|| S::part2(&parsed)));
+
+    Ok(Report {
+        part1,
+        part2,
+        timings: Timings {
+            parse,
+            part1: part1_time,
+            part2: part2_time,
+        },
+        allocations: parse_allocs.zip(part1_allocs).zip(part2_allocs).map(
+            |((parse, part1), part2)| Allocations {
+                parse,
+                part1,
+                part2,
+            },
+        ),
+    })
+}
+
+/// An entry in the day registry.
+pub struct Day {
+    pub year: usize,
+    pub number: usize,
+    run: fn(&str) -> Result<Report, ParseError>,
+    bench: fn(&str, usize) -> Result<BenchReport, ParseError>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(year: usize) -> Self {
+        Day {
+            year,
+            number: S::DAY,
+            run: run::<S>,
+            bench: bench::bench::<S>,
+        }
+    }
+
+    /// Runs the day against `input`, turning invalid input or a panic in the
+    /// solution into an error rather than taking down the whole process.
+    pub fn run(&self, input: &str) -> Result<Report, String> {
+        guarded(|| (self.run)(input))
+    }
+
+    pub fn bench(&self, input: &str, iterations: usize) -> Result<BenchReport, String> {
+        guarded(|| (self.bench)(input, iterations))
+    }
+}
+
+fn guarded<T>(f: impl FnOnce() -> Result<T, ParseError> + UnwindSafe) -> Result<T, String> {
+    let was_guarded = GUARDED.replace(true);
+    let result = catch_unwind(f);
+    GUARDED.set(was_guarded);
+
+    match result {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(payload) => Err(LAST_PANIC
+            .take()
+            .unwrap_or_else(|| format!("panicked: {}", panic_message(payload.as_ref())))),
+    }
+}
+
+/// Matches the main thread's stack, since some solutions recurse deeply.
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Maps `f` over `items` on up to `jobs` worker threads, returning the results
+/// in the same order as `items` however the work was interleaved.
+pub fn parallel_map<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            thread::Builder::new()
+                .stack_size(WORKER_STACK_SIZE)
+                .spawn_scoped(scope, || loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else {
+                        break;
+                    };
+
+                    let result = f(item);
+                    results.lock().unwrap()[i] = Some(result);
+                })
+                .expect("failed to spawn worker thread");
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item was processed"))
+        .collect()
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+    /// Whether this thread is inside `guarded`, where panics are reported as
+    /// errors instead of printed.
+    static GUARDED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Wraps the panic hook so that panics inside `Day::run` keep their message
+/// and location for it to report, rather than printing straight to stderr.
+/// Panics anywhere else still go to the previous hook.
+pub fn capture_panics() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !GUARDED.get() {
+                return previous(info);
+            }
+
+            let location = info
+                .location()
+                .map(|l| format!(" at {}", l))
+                .unwrap_or_default();
+            let message = format!("panicked{}: {}", location, panic_message(info.payload()));
+
+            LAST_PANIC.set(Some(message));
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg
+    } else {
+        "Box<dyn Any>"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_map_keeps_order() {
+        let items = (0..50).collect::<Vec<u64>>();
+        let results = parallel_map(&items, 4, |&n| {
+            thread::sleep(Duration::from_micros((50 - n) * 20));
+            n * n
+        });
+
+        assert_eq!(results, items.iter().map(|n| n * n).collect::<Vec<_>>());
+        assert!(parallel_map(&[] as &[u64], 4, |&n| n).is_empty());
+    }
+
+    #[test]
+    fn catches_panics_on_workers() {
+        let results = parallel_map(&[1, 0, 2], 3, |&n| {
+            guarded(|| {
+                assert_ne!(n, 0, "zero");
+                Ok(n)
+            })
+        });
+
+        assert_eq!(results[0], Ok(1));
+        assert!(results[1].as_ref().unwrap_err().contains("zero"));
+        assert_eq!(results[2], Ok(2));
+    }
+
+    #[test]
+    fn only_guards_inside_guarded() {
+        assert!(!GUARDED.get());
+        assert_eq!(
+            guarded(|| {
+                assert!(GUARDED.get());
+                Ok(())
+            }),
+            Ok(())
+        );
+        assert!(guarded::<()>(|| panic!("boom")).is_err());
+        assert!(!GUARDED.get());
+    }
+}
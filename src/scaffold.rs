@@ -0,0 +1,76 @@
+use std::{fs, io, path::PathBuf};
+
+use aoc_2024::input::day_dir;
+
+const TEMPLATE: &str = r#"use crate::{
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
+
+pub struct Day{NN};
+
+impl Solution for Day{NN} {
+    const DAY: usize = {N};
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(_input: &Self::Input<'_>) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(_input: &Self::Input<'_>) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<&str>, ParseError> {
+    Ok(input.lines().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+";
+
+    #[test]
+    fn solves_example() {
+        let input = Day{NN}::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day{NN}::part1(&input), Answer::Unsolved);
+        assert_eq!(Day{NN}::part2(&input), Answer::Unsolved);
+    }
+}
+"#;
+
+/// Writes a new day's module from the template, along with an empty
+/// `input.in`, returning the module's path. The build script picks the module
+/// up and registers it on the next build.
+pub fn new_day(year: usize, day: usize) -> io::Result<PathBuf> {
+    let dir = day_dir(year, day);
+    let module = dir.join("mod.rs");
+    if module.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module.display()),
+        ));
+    }
+
+    fs::create_dir_all(&dir)?;
+
+    let source = TEMPLATE
+        .replace("{NN}", &format!("{:02}", day))
+        .replace("{N}", &day.to_string());
+    fs::write(&module, source)?;
+
+    let input = dir.join("input.in");
+    if !input.exists() {
+        fs::write(input, "")?;
+    }
+
+    Ok(module)
+}
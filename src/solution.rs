@@ -0,0 +1,75 @@
+use std::fmt::Display;
+
+use crate::parse::ParseError;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// There is no solution for this part (e.g. the second half of day 25).
+    Unsolved,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "-"),
+        }
+    }
+}
+
+impl Answer {
+    /// The answer as text, or `None` if the part is unsolved.
+    pub fn solved(&self) -> Option<String> {
+        match self {
+            Answer::Unsolved => None,
+            answer => Some(answer.to_string()),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(usize, isize, u64, i64, u128, i128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// A day's puzzle, split into parsing and the two parts so that the harness
+/// can time and report each of them separately.
+pub trait Solution {
+    const DAY: usize;
+
+    /// The parsed puzzle input. It may borrow from the raw input text.
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    fn part1(input: &Self::Input<'_>) -> Answer;
+
+    fn part2(_input: &Self::Input<'_>) -> Answer {
+        Answer::Unsolved
+    }
+}
@@ -0,0 +1,243 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// What the puzzle server made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Correct,
+    /// Wrong, with no hint as to which way.
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The part had already been solved, so the answer wasn't checked.
+    AlreadySolved,
+}
+
+impl Outcome {
+    /// Recognises the outcome from the text of the server's response, which
+    /// is the only place it is given.
+    pub fn parse(response: &str) -> Option<Self> {
+        if response.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if response.contains("That's not the right answer") {
+            if response.contains("too high") {
+                Some(Outcome::TooHigh)
+            } else if response.contains("too low") {
+                Some(Outcome::TooLow)
+            } else {
+                Some(Outcome::Incorrect)
+            }
+        } else if response.contains("Did you already complete it") {
+            Some(Outcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Incorrect => write!(f, "wrong"),
+            Outcome::TooHigh => write!(f, "wrong (too high)"),
+            Outcome::TooLow => write!(f, "wrong (too low)"),
+            Outcome::AlreadySolved => write!(f, "not checked, the part is already solved"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub part: usize,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+/// Why an answer won't be submitted, going by earlier attempts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part is solved, with this answer if we submitted it.
+    Solved(Option<String>),
+    /// The same answer was already rejected.
+    KnownWrong,
+    /// The answer is at least an earlier one that was too high.
+    TooHigh(String),
+    /// The answer is at most an earlier one that was too low.
+    TooLow(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Solved(Some(answer)) => write!(f, "already solved with {}", answer),
+            Refusal::Solved(None) => write!(f, "already solved"),
+            Refusal::KnownWrong => write!(f, "already submitted and known to be wrong"),
+            Refusal::TooHigh(bound) => write!(f, "{} was already too high", bound),
+            Refusal::TooLow(bound) => write!(f, "{} was already too low", bound),
+        }
+    }
+}
+
+/// Every answer submitted for a day, kept in `submissions.toml` next to its
+/// input.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "attempt")]
+    pub attempts: Vec<Attempt>,
+}
+
+pub fn history_path(input_path: &Path) -> PathBuf {
+    input_path.with_file_name("submissions.toml")
+}
+
+impl History {
+    /// Loads the history file, treating a missing file as no attempts.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                toml::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let contents =
+            toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        fs::write(path, contents)
+    }
+
+    /// Checks `answer` against the earlier attempts at `part`, refusing it if
+    /// it can't be right.
+    pub fn check(&self, part: usize, answer: &str) -> Result<(), Refusal> {
+        let number = answer.parse::<i128>().ok();
+
+        for attempt in self.attempts.iter().filter(|attempt| attempt.part == part) {
+            let bound = attempt.answer.parse::<i128>().ok();
+
+            match attempt.outcome {
+                Outcome::Correct => return Err(Refusal::Solved(Some(attempt.answer.clone()))),
+                Outcome::AlreadySolved => return Err(Refusal::Solved(None)),
+                _ if attempt.answer == answer => return Err(Refusal::KnownWrong),
+                Outcome::TooHigh if number.zip(bound).is_some_and(|(n, b)| n >= b) => {
+                    return Err(Refusal::TooHigh(attempt.answer.clone()))
+                }
+                Outcome::TooLow if number.zip(bound).is_some_and(|(n, b)| n <= b) => {
+                    return Err(Refusal::TooLow(attempt.answer.clone()))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, part: usize, answer: &str, outcome: Outcome) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+
+        self.attempts.push(Attempt {
+            part,
+            answer: answer.to_string(),
+            outcome,
+            timestamp,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(attempts: &[(usize, &str, Outcome)]) -> History {
+        let mut history = History::default();
+        for &(part, answer, outcome) in attempts {
+            history.record(part, answer, outcome);
+        }
+
+        history
+    }
+
+    #[test]
+    fn recognises_outcomes() {
+        let wrong = "That's not the right answer; your answer is too low.  If you're stuck...";
+
+        assert_eq!(Outcome::parse(wrong), Some(Outcome::TooLow));
+        assert_eq!(
+            Outcome::parse("That's the right answer!  You are one gold star closer"),
+            Some(Outcome::Correct)
+        );
+        assert_eq!(
+            Outcome::parse(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Some(Outcome::AlreadySolved)
+        );
+        assert_eq!(Outcome::parse("<html></html>"), None);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let history = history(&[
+            (1, "abc", Outcome::Incorrect),
+            (2, "100", Outcome::Incorrect),
+        ]);
+
+        assert_eq!(history.check(1, "abc"), Err(Refusal::KnownWrong));
+        assert_eq!(history.check(1, "100"), Ok(()));
+        assert_eq!(history.check(2, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let history = history(&[(1, "100", Outcome::TooHigh), (1, "10", Outcome::TooLow)]);
+
+        assert_eq!(history.check(1, "100"), Err(Refusal::KnownWrong));
+        assert_eq!(
+            history.check(1, "150"),
+            Err(Refusal::TooHigh("100".to_string()))
+        );
+        assert_eq!(
+            history.check(1, "5"),
+            Err(Refusal::TooLow("10".to_string()))
+        );
+        assert_eq!(history.check(1, "50"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let history = history(&[
+            (1, "42", Outcome::Correct),
+            (2, "7", Outcome::AlreadySolved),
+        ]);
+
+        assert_eq!(
+            history.check(1, "43"),
+            Err(Refusal::Solved(Some("42".to_string())))
+        );
+        assert_eq!(history.check(2, "8"), Err(Refusal::Solved(None)));
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let history = history(&[(1, "100", Outcome::TooHigh)]);
+        let contents = toml::to_string(&history).unwrap();
+
+        assert!(contents.contains("[[attempt]]"));
+        assert!(contents.contains("outcome = \"too-high\""));
+        assert_eq!(toml::from_str::<History>(&contents).unwrap(), history);
+    }
+}
@@ -0,0 +1,111 @@
+use std::{fmt::Display, time::Duration};
+
+/// A plain-text table with left-aligned columns.
+///
+/// A row with fewer cells than there are headers lets its last cell run on
+/// across the remaining columns, which is how failed days are shown.
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Table {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    fn widths(&self) -> Vec<usize> {
+        let mut widths = self
+            .headers
+            .iter()
+            .map(|h| h.chars().count())
+            .collect::<Vec<_>>();
+
+        for row in &self.rows {
+            if row.len() < widths.len() {
+                continue;
+            }
+
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        widths
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let widths = self.widths();
+
+        let write_row = |f: &mut std::fmt::Formatter<'_>, cells: &[String]| {
+            let line = cells
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(i, (cell, width))| {
+                    if i + 1 == cells.len() {
+                        cell.to_string()
+                    } else {
+                        format!("{:<width$}", cell, width = width)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" | ");
+
+            writeln!(f, "{}", line)
+        };
+
+        write_row(f, &self.headers)?;
+        writeln!(
+            f,
+            "{}",
+            widths
+                .iter()
+                .map(|w| "-".repeat(*w))
+                .collect::<Vec<_>>()
+                .join("-+-")
+        )?;
+
+        for row in &self.rows {
+            write_row(f, row)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats a duration with a unit that keeps it readable, so that fast days
+/// don't all show up as `0ms`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+/// Formats a number of bytes in binary units.
+pub fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{}B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1}KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1}MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
@@ -0,0 +1,1090 @@
+#![allow(dead_code)]
+mod ratio;
+pub mod region;
+pub mod search;
+mod union_find;
+
+pub use ratio::{Integer, Ratio};
+pub use union_find::UnionFind;
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub},
+};
+
+use crate::parse::{self, ParseError};
+
+#[derive(Debug, Clone)]
+pub struct Counter<T: Hash + Eq> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Hash + Eq> Counter<T> {
+    pub fn new() -> Self {
+        Counter {
+            counts: HashMap::default(),
+        }
+    }
+
+    pub fn count(&mut self, item: T) {
+        self.counts.entry(item).or_default().add_assign(1);
+    }
+
+    pub fn count_n(&mut self, item: T, n: usize) {
+        self.counts.entry(item).or_default().add_assign(n);
+    }
+
+    pub fn count_all<U: Iterator<Item = T>>(&mut self, items: U) {
+        items.for_each(|it| self.count(it));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(t, u)| (t, *u))
+    }
+
+    pub fn get(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// The number of distinct items counted.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The `n` items with the highest counts, highest first. Ties come out in
+    /// no particular order.
+    pub fn most_common(&self, n: usize) -> Vec<(&T, usize)> {
+        let mut counts = self.iter().collect::<Vec<_>>();
+        counts.sort_unstable_by_key(|&(_, count)| std::cmp::Reverse(count));
+        counts.truncate(n);
+
+        counts
+    }
+
+    /// The item with the highest count, or any one of them if there is a tie.
+    pub fn max_by_count(&self) -> Option<(&T, usize)> {
+        self.iter().max_by_key(|&(_, count)| count)
+    }
+
+    /// Adds every count in `other` to this one.
+    pub fn merge(&mut self, other: Counter<T>) {
+        for (item, count) in other {
+            self.count_n(item, count);
+        }
+    }
+
+    pub fn retain(&mut self, mut f: impl FnMut(&T, usize) -> bool) {
+        self.counts.retain(|item, count| f(item, *count));
+    }
+}
+
+impl<T: Hash + Eq> Add for Counter<T> {
+    type Output = Counter<T>;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self.merge(rhs);
+
+        self
+    }
+}
+
+impl<T: Hash + Eq> Sub for Counter<T> {
+    type Output = Counter<T>;
+
+    /// Takes away the counts in `rhs`, dropping any item whose count reaches
+    /// zero rather than going negative.
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        for (item, count) in rhs {
+            if let Some(existing) = self.counts.get_mut(&item) {
+                if *existing > count {
+                    *existing -= count;
+                } else {
+                    self.counts.remove(&item);
+                }
+            }
+        }
+
+        self
+    }
+}
+
+impl<T: Hash + Eq> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = std::collections::hash_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<'a, T: Hash + Eq> IntoIterator for &'a Counter<T> {
+    type Item = (&'a T, usize);
+    type IntoIter = std::iter::Map<
+        std::collections::hash_map::Iter<'a, T, usize>,
+        fn((&'a T, &'a usize)) -> (&'a T, usize),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.iter().map(|(item, &count)| (item, count))
+    }
+}
+
+impl<T: Hash + Eq> Extend<T> for Counter<T> {
+    fn extend<U: IntoIterator<Item = T>>(&mut self, iter: U) {
+        self.count_all(iter.into_iter());
+    }
+}
+
+impl<T: Hash + Eq> Default for Counter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Counter<T> {
+    fn from_iter<U: IntoIterator<Item = T>>(iter: U) -> Self {
+        let mut counter = Self::new();
+
+        counter.count_all(iter.into_iter());
+
+        counter
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct Coord(pub isize, pub isize);
+
+impl Coord {
+    pub fn move_(self, direction: Direction) -> Self {
+        let Coord(x, y) = self;
+
+        match direction {
+            Direction::Up => Coord(x, y - 1),
+            Direction::Down => Coord(x, y + 1),
+            Direction::Left => Coord(x - 1, y),
+            Direction::Right => Coord(x + 1, y),
+        }
+    }
+
+    pub fn adjacent(self) -> [Coord; 4] {
+        [
+            self.move_(Direction::Right),
+            self.move_(Direction::Left),
+            self.move_(Direction::Up),
+            self.move_(Direction::Down),
+        ]
+    }
+
+    pub fn move_while<F: Fn(Coord) -> bool>(self, direction: Direction, predicate: F) -> Coord {
+        let mut curr = self;
+        while predicate(curr.move_(direction)) {
+            curr = curr.move_(direction);
+        }
+
+        curr
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> usize {
+        (self.0 - other.0).unsigned_abs() + (self.1 - other.1).unsigned_abs()
+    }
+
+    /// The distance when diagonal steps are allowed, i.e. the number of king
+    /// moves between the two.
+    pub fn chebyshev_distance(self, other: Self) -> usize {
+        (self.0 - other.0)
+            .unsigned_abs()
+            .max((self.1 - other.1).unsigned_abs())
+    }
+
+    pub fn moved_by(self, direction: Direction, n: isize) -> Self {
+        self + Coord::from(direction) * n
+    }
+
+    /// Every coordinate from this one onwards in steps of `step`, starting with
+    /// this one. The ray is endless, so bound it with [`Ray::within`] or
+    /// similar.
+    pub fn ray(self, step: impl Into<Coord>) -> Ray {
+        Ray {
+            next: self,
+            step: step.into(),
+        }
+    }
+
+    pub fn move8(self, direction: Direction8) -> Self {
+        let (dx, dy) = direction.to_vector();
+
+        Coord(self.0 + dx, self.1 + dy)
+    }
+
+    /// The eight surrounding coordinates, clockwise from straight up.
+    pub fn neighbors8(self) -> [Coord; 8] {
+        Direction8::all_directions().map(|direction| self.move8(direction))
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Coord(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Coord(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl Mul<isize> for Coord {
+    type Output = Coord;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Coord(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl Neg for Coord {
+    type Output = Coord;
+
+    fn neg(self) -> Self::Output {
+        Coord(-self.0, -self.1)
+    }
+}
+
+impl From<Direction> for Coord {
+    /// The unit step in `direction`.
+    fn from(direction: Direction) -> Self {
+        Coord(0, 0).move_(direction)
+    }
+}
+
+/// The endless line of coordinates from [`Coord::ray`].
+#[derive(Debug, Clone)]
+pub struct Ray {
+    next: Coord,
+    step: Coord,
+}
+
+impl Ray {
+    /// Stops the ray at the edge of `grid`.
+    pub fn within<T>(self, grid: &Grid<T>) -> impl Iterator<Item = Coord> + '_ {
+        self.take_while(|&coord| grid.contains(coord))
+    }
+}
+
+impl Iterator for Ray {
+    type Item = Coord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let coord = self.next;
+        self.next = coord + self.step;
+
+        Some(coord)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn all_directions() -> [Direction; 4] {
+        [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Down => Direction::Up,
+            Direction::Up => Direction::Down,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// The direction after a quarter turn clockwise, with `y` growing
+    /// downwards.
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.turn_right().opposite()
+    }
+
+    pub fn perpendicular_directions(self) -> [Direction; 2] {
+        match self {
+            Direction::Up | Direction::Down => [Direction::Left, Direction::Right],
+            Direction::Left | Direction::Right => [Direction::Up, Direction::Down],
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        self == Direction::Up || self == Direction::Down
+    }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            '<' => Self::Left,
+            '>' => Self::Right,
+            '^' => Self::Up,
+            'v' => Self::Down,
+            _ => return None,
+        })
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Self::Left => '<',
+            Self::Right => '>',
+            Self::Up => '^',
+            Self::Down => 'v',
+        }
+    }
+}
+
+/// A direction including the diagonals, for when [`Direction`] isn't enough.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from straight up.
+    pub fn all_directions() -> [Direction8; 8] {
+        [
+            Direction8::Up,
+            Direction8::UpRight,
+            Direction8::Right,
+            Direction8::DownRight,
+            Direction8::Down,
+            Direction8::DownLeft,
+            Direction8::Left,
+            Direction8::UpLeft,
+        ]
+    }
+
+    pub fn diagonals() -> [Direction8; 4] {
+        [
+            Direction8::UpRight,
+            Direction8::DownRight,
+            Direction8::DownLeft,
+            Direction8::UpLeft,
+        ]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        Self::diagonals().contains(&self)
+    }
+
+    /// The step in `(x, y)` that moving this way takes, with `y` growing
+    /// downwards.
+    pub fn to_vector(self) -> (isize, isize) {
+        match self {
+            Direction8::Up => (0, -1),
+            Direction8::UpRight => (1, -1),
+            Direction8::Right => (1, 0),
+            Direction8::DownRight => (1, 1),
+            Direction8::Down => (0, 1),
+            Direction8::DownLeft => (-1, 1),
+            Direction8::Left => (-1, 0),
+            Direction8::UpLeft => (-1, -1),
+        }
+    }
+
+    fn rotate(self, eighths: usize) -> Self {
+        let all = Self::all_directions();
+        let i = all.iter().position(|&d| d == self).unwrap();
+
+        all[(i + eighths) % 8]
+    }
+
+    /// Turns 45° clockwise.
+    pub fn rotate_cw(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Turns 45° anticlockwise.
+    pub fn rotate_ccw(self) -> Self {
+        self.rotate(7)
+    }
+
+    pub fn rotate_cw_90(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn rotate_ccw_90(self) -> Self {
+        self.rotate(6)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Direction8::Up,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+            Direction::Right => Direction8::Right,
+        }
+    }
+}
+
+/// A rectangular grid, stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid from its cells in row order.
+    ///
+    /// # Panics
+    ///
+    /// If there aren't exactly `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {}x{} grid needs {} cells",
+            width,
+            height,
+            width * height
+        );
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// If the rows aren't all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "grid rows must all be the same length"
+        );
+
+        Grid::new(width, height, rows.into_iter().flatten().collect())
+    }
+
+    /// Parses each character of each line of `input` with `cell`, which is
+    /// given the character and its position and returns `None` to reject it.
+    /// Lines that are longer or shorter than the first are rejected too.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char, Coord) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let rows = parse::grid(input, expected, |c, x, y| {
+            cell(c, Coord(x as isize, y as isize))
+        })?;
+
+        let width = rows.first().map_or(0, |row| row.len());
+        for (line, row) in input.lines().zip(&rows) {
+            if row.len() > width {
+                let (extra, _) = line.char_indices().nth(width).unwrap();
+                return Err(ParseError::at(input, &line[extra..], "end of line"));
+            } else if row.len() < width {
+                return Err(ParseError::at(
+                    input,
+                    &line[line.len()..],
+                    format!("{} cells in every row", width),
+                ));
+            }
+        }
+
+        Ok(Grid::from_rows(rows))
+    }
+
+    /// Parses a grid like [`Grid::parse`], where `tile` says what each
+    /// character stands for and which are markers, such as a start or end
+    /// position, whose coordinates are collected as well.
+    pub fn parse_with(
+        input: &str,
+        expected: &str,
+        mut tile: impl FnMut(char) -> Option<Tile<T>>,
+    ) -> Result<(Self, Markers), ParseError> {
+        let mut markers = Markers::default();
+        let grid = Grid::parse(input, expected, |c, coord| match tile(c)? {
+            Tile::Cell(cell) => Some(cell),
+            Tile::Marker(cell) => {
+                markers.0.entry(c).or_default().push(coord);
+                Some(cell)
+            }
+        })?;
+
+        Ok((grid, markers))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, Coord(x, y): Coord) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    fn index_of(&self, coord: Coord) -> Option<usize> {
+        self.contains(coord)
+            .then(|| coord.1 as usize * self.width + coord.0 as usize)
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.index_of(coord).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.index_of(coord).map(|i| &mut self.cells[i])
+    }
+
+    /// Sets the cell at `coord`, returning whether it is in the grid.
+    pub fn set(&mut self, coord: Coord, val: T) -> bool {
+        match self.get_mut(coord) {
+            Some(cell) => {
+                *cell = val;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is out of bounds", x);
+
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.col(x))
+    }
+
+    /// Every coordinate in the grid, row by row.
+    pub fn iter_coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Coord(x as isize, y as isize)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.iter_coords().zip(&self.cells)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+/// What a character stands for when parsing with [`Grid::parse_with`].
+pub enum Tile<T> {
+    Cell(T),
+    /// A cell whose position is recorded under its character.
+    Marker(T),
+}
+
+/// The positions of each marker character found by [`Grid::parse_with`], in
+/// the order they appear.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Markers(HashMap<char, Vec<Coord>>);
+
+impl Markers {
+    pub fn all(&self, marker: char) -> &[Coord] {
+        self.0.get(&marker).map_or(&[], |coords| coords.as_slice())
+    }
+
+    pub fn first(&self, marker: char) -> Option<Coord> {
+        self.all(marker).first().copied()
+    }
+
+    /// The position of a marker that must appear in `input`, failing at the
+    /// end of the input if it doesn't.
+    pub fn require(&self, input: &str, marker: char, expected: &str) -> Result<Coord, ParseError> {
+        self.first(marker)
+            .ok_or_else(|| ParseError::at(input, &input[input.len()..], expected))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (char, &[Coord])> {
+        self.0
+            .iter()
+            .map(|(&marker, coords)| (marker, coords.as_slice()))
+    }
+}
+
+impl<T: Default> Grid<T> {
+    pub fn with_dimensions(width: usize, height: usize) -> Self {
+        Grid::new(
+            width,
+            height,
+            (0..width * height).map(|_| T::default()).collect(),
+        )
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", coord))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", coord))
+    }
+}
+
+impl<T: Clone + Into<char>> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render().fmt(f)
+    }
+}
+
+const PATH_COLOUR: &str = "\x1b[33m";
+const HIGHLIGHT_COLOUR: &str = "\x1b[32m";
+const MARKER_COLOUR: &str = "\x1b[1;31m";
+const RESET_COLOUR: &str = "\x1b[0m";
+
+/// Draws a grid with overlays on top, for debugging. Later overlays are
+/// drawn over earlier ones.
+///
+/// ```
+/// # use std::collections::HashSet;
+/// # use aoc_2024::util::{parse_char_grid, Coord};
+/// let grid = parse_char_grid("...\n...\n");
+/// let rendered = grid
+///     .render()
+///     .path(&[Coord(0, 0), Coord(1, 0), Coord(1, 1)])
+///     .highlight(&HashSet::from([Coord(2, 1)]), 'o')
+///     .marker(Coord(0, 1), 'S')
+///     .to_string();
+///
+/// assert_eq!(rendered, ">v.\nS*o\n");
+/// ```
+pub struct Render<'a, T> {
+    grid: &'a Grid<T>,
+    cell: Box<dyn Fn(&T) -> char + 'a>,
+    overlays: Vec<(HashMap<Coord, char>, &'static str)>,
+    colour: bool,
+}
+
+impl<T> Grid<T> {
+    /// Starts rendering the grid, drawing each cell with `cell`.
+    pub fn render_with<'a>(&'a self, cell: impl Fn(&T) -> char + 'a) -> Render<'a, T> {
+        Render {
+            grid: self,
+            cell: Box::new(cell),
+            overlays: vec![],
+            colour: false,
+        }
+    }
+}
+
+impl<T: Clone + Into<char>> Grid<T> {
+    pub fn render(&self) -> Render<'_, T> {
+        self.render_with(|cell| cell.clone().into())
+    }
+}
+
+impl<T> Render<'_, T> {
+    /// Draws a path as arrows from each step to the next, ending in `*`.
+    pub fn path(mut self, path: &[Coord]) -> Self {
+        let mut overlay = HashMap::new();
+        for (i, &coord) in path.iter().enumerate() {
+            let arrow = path.get(i + 1).and_then(|&next| {
+                Direction::all_directions()
+                    .into_iter()
+                    .find(|&dir| coord.move_(dir) == next)
+            });
+            overlay.insert(coord, arrow.map_or('*', Direction::to_char));
+        }
+
+        self.overlays.push((overlay, PATH_COLOUR));
+        self
+    }
+
+    /// Draws `c` over every coordinate in `coords`.
+    pub fn highlight(mut self, coords: &HashSet<Coord>, c: char) -> Self {
+        let overlay = coords.iter().map(|&coord| (coord, c)).collect();
+
+        self.overlays.push((overlay, HIGHLIGHT_COLOUR));
+        self
+    }
+
+    pub fn marker(mut self, coord: Coord, c: char) -> Self {
+        self.overlays
+            .push((HashMap::from([(coord, c)]), MARKER_COLOUR));
+        self
+    }
+
+    /// Whether to colour the overlays with ANSI escape codes.
+    pub fn colour(mut self, colour: bool) -> Self {
+        self.colour = colour;
+        self
+    }
+}
+
+impl<T> Display for Render<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (coord, cell) in self.grid.iter() {
+            let overlay = self
+                .overlays
+                .iter()
+                .rev()
+                .find_map(|(overlay, colour)| Some((*overlay.get(&coord)?, *colour)));
+
+            match overlay {
+                Some((c, colour)) if self.colour => write!(f, "{}{}{}", colour, c, RESET_COLOUR)?,
+                Some((c, _)) => write!(f, "{}", c)?,
+                None => write!(f, "{}", (self.cell)(cell))?,
+            }
+
+            if coord.0 as usize + 1 == self.grid.width {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// # Panics
+///
+/// If the lines of `input` aren't all the same length.
+pub fn parse_char_grid(input: &str) -> Grid<char> {
+    Grid::from_rows(input.lines().map(|line| line.chars().collect()).collect())
+}
+
+pub fn gcd(a: isize, b: isize) -> isize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub struct ComboIter<'a, T> {
+    options: &'a [T],
+    len: usize,
+    curr: usize,
+}
+
+pub fn all_combos_with_length<T: Copy + Default>(options: &[T], len: usize) -> ComboIter<'_, T> {
+    ComboIter {
+        options,
+        len,
+        curr: 0,
+    }
+}
+
+impl<'a, T> Iterator for ComboIter<'a, T>
+where
+    T: Copy + Default,
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.curr >= self.options.len().pow(self.len as u32) {
+            return None;
+        }
+
+        let mut curr = self.curr;
+        self.curr += 1;
+
+        let mut combo = vec![T::default(); self.len];
+        for item in combo.iter_mut() {
+            *item = self.options[curr % self.options.len()];
+            curr /= self.options.len();
+        }
+
+        Some(combo)
+    }
+}
+
+pub fn permutations<T: Copy>(src: &mut [T], n: usize) -> Vec<Vec<T>> {
+    if n <= 1 {
+        return vec![src.to_vec()];
+    }
+
+    let mut perms = vec![];
+
+    perms.extend(permutations(src, n - 1));
+
+    for i in 0..n - 1 {
+        if n.is_multiple_of(2) {
+            src.swap(i, n - 1)
+        } else {
+            src.swap(0, n - 1)
+        }
+        perms.extend(permutations(src, n - 1));
+    }
+
+    perms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_most_common() {
+        let counter = "abracadabra".chars().collect::<Counter<_>>();
+
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.total(), 11);
+        assert_eq!(counter.most_common(1), vec![(&'a', 5)]);
+        assert_eq!(
+            counter
+                .most_common(3)
+                .into_iter()
+                .map(|(_, count)| count)
+                .collect::<Vec<_>>(),
+            vec![5, 2, 2]
+        );
+        assert_eq!(counter.most_common(10).len(), 5);
+        assert_eq!(counter.max_by_count(), Some((&'a', 5)));
+        assert_eq!(Counter::<char>::new().max_by_count(), None);
+    }
+
+    #[test]
+    fn combines_counters() {
+        let a = "aab".chars().collect::<Counter<_>>();
+        let b = "abc".chars().collect::<Counter<_>>();
+
+        let sum = a.clone() + b.clone();
+        assert_eq!((sum.get(&'a'), sum.get(&'b'), sum.get(&'c')), (3, 2, 1));
+
+        let difference = a.clone() - b;
+        assert_eq!(difference.len(), 1);
+        assert_eq!(difference.get(&'a'), 1);
+
+        let mut merged = a;
+        merged.extend("cc".chars());
+        merged.retain(|_, count| count > 1);
+        let mut items = merged.into_iter().collect::<Vec<_>>();
+        items.sort();
+        assert_eq!(items, vec![('a', 2), ('c', 2)]);
+    }
+
+    #[test]
+    fn indexes_flat_grid() {
+        let mut grid = parse_char_grid("abc\ndef\n");
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Coord(2, 1)], 'f');
+        assert_eq!(grid.get(Coord(3, 0)), None);
+        assert!(!grid.contains(Coord(0, -1)));
+
+        grid[Coord(0, 1)] = 'x';
+        *grid.get_mut(Coord(1, 0)).unwrap() = 'y';
+        assert_eq!(grid.row(0), ['a', 'y', 'c']);
+        assert_eq!(grid.row(1), ['x', 'e', 'f']);
+    }
+
+    #[test]
+    fn views_rows_and_columns() {
+        let grid = parse_char_grid("abc\ndef\n");
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        assert_eq!(
+            grid.cols()
+                .map(|col| col.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(grid.iter().nth(4), Some((Coord(1, 1), &'e')));
+        assert_eq!(
+            grid.map(|c| c.is_ascii_lowercase()).get(Coord(2, 1)),
+            Some(&true)
+        );
+    }
+
+    #[test]
+    fn parses_rectangular_grids() {
+        let grid = Grid::parse("12\n34\n", "a digit", |c, _| c.to_digit(10)).unwrap();
+
+        assert_eq!(grid, Grid::new(2, 2, vec![1, 2, 3, 4]));
+
+        let error = Grid::parse("12\n345\n", "a digit", |c, _| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.found.as_str()),
+            (2, 3, "`5`")
+        );
+
+        let error = Grid::parse("12\n3\n", "a digit", |c, _| c.to_digit(10)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "2 cells in every row");
+    }
+
+    #[test]
+    fn does_coord_arithmetic() {
+        let a = Coord(3, -2);
+        let b = Coord(1, 4);
+
+        assert_eq!(a + b, Coord(4, 2));
+        assert_eq!(a - b, Coord(2, -6));
+        assert_eq!(a * 2 - b, Coord(5, -8));
+        assert_eq!(-a, Coord(-3, 2));
+        assert_eq!(a.chebyshev_distance(b), 6);
+        assert_eq!(a.moved_by(Direction::Up, 3), Coord(3, -5));
+        assert_eq!(Coord::from(Direction::Left), Coord(-1, 0));
+    }
+
+    #[test]
+    fn turns() {
+        for direction in Direction::all_directions() {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert!(direction
+                .perpendicular_directions()
+                .contains(&direction.turn_right()));
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    }
+
+    #[test]
+    fn rays_stop_at_the_edge() {
+        let grid = Grid::new(4, 3, vec![(); 12]);
+
+        assert_eq!(
+            Coord(0, 0)
+                .ray(Coord(2, 1))
+                .within(&grid)
+                .collect::<Vec<_>>(),
+            vec![Coord(0, 0), Coord(2, 1)]
+        );
+        assert_eq!(Coord(3, 1).ray(Direction::Left).within(&grid).count(), 4);
+        assert_eq!(Coord(5, 5).ray(Direction::Up).within(&grid).count(), 0);
+    }
+
+    #[test]
+    fn rotates_eight_ways() {
+        assert_eq!(Direction8::Up.rotate_cw(), Direction8::UpRight);
+        assert_eq!(Direction8::Up.rotate_ccw(), Direction8::UpLeft);
+        assert_eq!(Direction8::UpLeft.rotate_cw_90(), Direction8::UpRight);
+        assert_eq!(Direction8::Left.rotate_ccw_90(), Direction8::Down);
+        assert_eq!(Direction8::DownRight.opposite(), Direction8::UpLeft);
+        assert!(Direction8::DownLeft.is_diagonal());
+        assert_eq!(Direction8::from(Direction::Left).to_vector(), (-1, 0));
+    }
+
+    #[test]
+    fn finds_eight_neighbours() {
+        let neighbors = Coord(0, 0).neighbors8();
+
+        assert_eq!(neighbors[0], Coord(0, -1));
+        assert_eq!(neighbors[3], Coord(1, 1));
+        assert_eq!(neighbors[7], Coord(-1, -1));
+        assert!(!neighbors.contains(&Coord(0, 0)));
+    }
+
+    #[test]
+    fn renders_overlays() {
+        let grid = parse_char_grid("#..\n...\n");
+
+        assert_eq!(grid.to_string(), "#..\n...\n");
+        assert_eq!(
+            grid.render()
+                .path(&[Coord(1, 0), Coord(1, 1), Coord(2, 1)])
+                .marker(Coord(2, 1), 'E')
+                .to_string(),
+            "#v.\n.>E\n"
+        );
+        assert_eq!(
+            grid.render_with(|&c| if c == '#' { '█' } else { ' ' })
+                .highlight(&HashSet::from([Coord(0, 1)]), 'o')
+                .colour(true)
+                .to_string(),
+            "█  \n\x1b[32mo\x1b[0m  \n"
+        );
+    }
+
+    #[test]
+    fn parses_markers() {
+        let input = "S.a\n#a.\n..E\n";
+        let (grid, markers) = Grid::parse_with(input, "a maze tile", |c| match c {
+            '.' => Some(Tile::Cell(true)),
+            '#' => Some(Tile::Cell(false)),
+            'S' | 'E' | 'a' => Some(Tile::Marker(true)),
+            _ => None,
+        })
+        .unwrap();
+
+        assert!(!grid[Coord(0, 1)]);
+        assert_eq!(markers.first('S'), Some(Coord(0, 0)));
+        assert_eq!(markers.all('a'), [Coord(2, 0), Coord(1, 1)]);
+        assert_eq!(markers.require(input, 'E', "an end"), Ok(Coord(2, 2)));
+
+        let error = markers.require(input, 'X', "an exit `X`").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.found, "end of input");
+
+        let error = Grid::parse_with("S.\n.?\n", "a maze tile", |c| match c {
+            '.' => Some(Tile::Cell(())),
+            'S' => Some(Tile::Marker(())),
+            _ => None,
+        })
+        .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+}
@@ -0,0 +1,430 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Add, Div, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use crate::parse::{self, ParseError};
+
+/// The signed integer types a [`Ratio`] can be built from.
+pub trait Integer: Copy + Ord + Hash + Debug + Display + FromStr {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+    fn checked_neg(self) -> Option<Self>;
+    fn div_euclid(self, rhs: Self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_div(self, rhs)
+                }
+
+                fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_rem(self, rhs)
+                }
+
+                fn checked_neg(self) -> Option<Self> {
+                    <$t>::checked_neg(self)
+                }
+
+                fn div_euclid(self, rhs: Self) -> Self {
+                    <$t>::div_euclid(self, rhs)
+                }
+
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$t>::rem_euclid(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, isize);
+
+/// The positive greatest common divisor of `a` and `b`, where `b > 0`.
+fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        // Neither overflows: `|b|` only shrinks from a positive start.
+        (a, b) = (b, a.checked_rem(b).unwrap());
+    }
+
+    if a < T::ZERO {
+        a.checked_neg().unwrap()
+    } else {
+        a
+    }
+}
+
+/// An exact fraction, always kept in lowest terms with a positive
+/// denominator, so equal values have equal fields.
+///
+/// The `checked_` operations give `None` on overflow, while the operators
+/// panic, like the integer types' own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio<T = i128> {
+    numerator: T,
+    denominator: T,
+}
+
+impl<T: Integer> Ratio<T> {
+    /// # Panics
+    ///
+    /// If `denominator` is zero, or the ratio can't be put in lowest terms
+    /// without overflowing.
+    pub fn new(numerator: T, denominator: T) -> Self {
+        Self::checked_new(numerator, denominator).expect("invalid ratio")
+    }
+
+    /// The ratio in lowest terms, or `None` if `denominator` is zero.
+    pub fn checked_new(numerator: T, denominator: T) -> Option<Self> {
+        if denominator == T::ZERO {
+            return None;
+        }
+
+        let (numerator, denominator) = if denominator < T::ZERO {
+            (numerator.checked_neg()?, denominator.checked_neg()?)
+        } else {
+            (numerator, denominator)
+        };
+        let div = gcd(numerator, denominator);
+
+        Some(Ratio {
+            numerator: numerator.checked_div(div)?,
+            denominator: denominator.checked_div(div)?,
+        })
+    }
+
+    pub fn numerator(self) -> T {
+        self.numerator
+    }
+
+    /// Always positive.
+    pub fn denominator(self) -> T {
+        self.denominator
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.numerator < T::ZERO
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.denominator == T::ONE
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(self) -> Option<T> {
+        self.is_integer().then_some(self.numerator)
+    }
+
+    pub fn floor(self) -> T {
+        self.numerator.div_euclid(self.denominator)
+    }
+
+    pub fn ceil(self) -> T {
+        if self.is_integer() {
+            self.numerator
+        } else {
+            self.floor().checked_add(T::ONE).unwrap()
+        }
+    }
+
+    /// # Panics
+    ///
+    /// If the numerator is the type's minimum, as with the integers.
+    pub fn abs(self) -> Self {
+        if self.is_negative() {
+            -self
+        } else {
+            self
+        }
+    }
+
+    /// One over this ratio, or `None` for zero.
+    pub fn recip(self) -> Option<Self> {
+        Self::checked_new(self.denominator, self.numerator)
+    }
+
+    /// Whether `self` is a whole number of `other`s.
+    pub fn is_multiple_of(self, other: Self) -> bool {
+        self.checked_div(other).is_some_and(Self::is_integer)
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        // Scaling by the lcm of the denominators, rather than their product,
+        // keeps the intermediate values small.
+        let div = gcd(self.denominator, rhs.denominator);
+        let lhs_scale = rhs.denominator.checked_div(div)?;
+        let rhs_scale = self.denominator.checked_div(div)?;
+
+        Self::checked_new(
+            self.numerator
+                .checked_mul(lhs_scale)?
+                .checked_add(rhs.numerator.checked_mul(rhs_scale)?)?,
+            self.denominator.checked_mul(lhs_scale)?,
+        )
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        // Cancelling across first keeps the products as small as they can be.
+        let a = gcd(self.numerator, rhs.denominator);
+        let b = gcd(rhs.numerator, self.denominator);
+
+        Self::checked_new(
+            (self.numerator.checked_div(a)?).checked_mul(rhs.numerator.checked_div(b)?)?,
+            (self.denominator.checked_div(b)?).checked_mul(rhs.denominator.checked_div(a)?)?,
+        )
+    }
+
+    /// `None` if `rhs` is zero, as well as on overflow.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.checked_mul(rhs.recip()?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Ratio {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+}
+
+impl<T: Integer> Display for Ratio<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+impl<T: Integer> FromStr for Ratio<T> {
+    type Err = ParseError;
+
+    /// Parses `a/b`, or a plain integer `a`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((numerator, denominator)) = s.split_once('/') else {
+            return Ok(Ratio::from(parse::number::<T>(s, s.trim())?));
+        };
+
+        let numerator = parse::number(s, numerator.trim())?;
+        let denominator = denominator.trim();
+        let value = parse::number(s, denominator)?;
+
+        Self::checked_new(numerator, value)
+            .ok_or_else(|| ParseError::at(s, denominator, "a non-zero denominator"))
+    }
+}
+
+impl<T: Integer> From<T> for Ratio<T> {
+    fn from(value: T) -> Self {
+        Ratio {
+            numerator: value,
+            denominator: T::ONE,
+        }
+    }
+}
+
+impl<T: Integer> Ord for Ratio<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compares the whole parts, then the flipped fractional parts, like
+        // expanding both as continued fractions. Cross-multiplying instead
+        // could overflow.
+        let (mut a, mut b) = (self.numerator, self.denominator);
+        let (mut c, mut d) = (other.numerator, other.denominator);
+        let mut flipped = false;
+
+        loop {
+            let ordering = a.div_euclid(b).cmp(&c.div_euclid(d));
+            if ordering != Ordering::Equal {
+                return if flipped {
+                    ordering.reverse()
+                } else {
+                    ordering
+                };
+            }
+
+            let (r, s) = (a.rem_euclid(b), c.rem_euclid(d));
+            let ordering = match (r == T::ZERO, s == T::ZERO) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                (false, false) => {
+                    // r/b against s/d is the reverse of b/r against d/s.
+                    (a, b, c, d) = (b, r, d, s);
+                    flipped = !flipped;
+                    continue;
+                }
+            };
+
+            return if flipped {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+        }
+    }
+}
+
+impl<T: Integer> PartialOrd for Ratio<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Integer> Add for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("attempt to add with overflow")
+    }
+}
+
+impl<T: Integer> Sub for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl<T: Integer> Mul for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("attempt to multiply with overflow")
+    }
+}
+
+impl<T: Integer> Div for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs)
+            .expect("attempt to divide by zero or with overflow")
+    }
+}
+
+impl<T: Integer> Neg for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("attempt to negate with overflow")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(n: i128, d: i128) -> Ratio {
+        Ratio::new(n, d)
+    }
+
+    #[test]
+    fn keeps_canonical_form() {
+        assert_eq!(r(2, -4), r(-1, 2));
+        assert_eq!(r(-1, 2).denominator(), 2);
+        assert_eq!(r(0, -5), Ratio::from(0));
+        assert_eq!(Ratio::<i32>::checked_new(1, 0), None);
+        assert_eq!(Ratio::<i8>::checked_new(1, i8::MIN), None);
+        assert_eq!(r(6, 4).to_string(), "3/2");
+    }
+
+    #[test]
+    fn does_arithmetic() {
+        assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+        assert_eq!(r(1, 2) - r(2, 3), r(-1, 6));
+        assert_eq!(r(2, 3) * r(3, 4), r(1, 2));
+        assert_eq!(r(2, 3) / r(-4, 3), r(-1, 2));
+        assert_eq!(r(-3, 4).abs(), r(3, 4));
+        assert_eq!(r(-3, 4).recip(), Some(r(-4, 3)));
+        assert_eq!(r(0, 1).recip(), None);
+        assert!(r(3, 2).is_multiple_of(r(1, 2)));
+        assert!(!r(3, 2).is_multiple_of(r(2, 3)));
+    }
+
+    #[test]
+    fn checks_for_overflow() {
+        let big = Ratio::<i64>::from(i64::MAX);
+
+        assert_eq!(big.checked_add(Ratio::from(1)), None);
+        assert_eq!(
+            big.checked_mul(Ratio::new(1, 2)),
+            Some(Ratio::new(i64::MAX, 2))
+        );
+        assert_eq!(Ratio::from(i64::MIN).checked_neg(), None);
+        assert_eq!(big.checked_div(Ratio::from(0)), None);
+
+        // Cancelling across keeps this in range, though `MAX * MAX` isn't.
+        let half = Ratio::new(i64::MAX, 2);
+        assert_eq!(half * Ratio::new(2, i64::MAX), Ratio::from(1));
+    }
+
+    #[test]
+    fn rounds() {
+        assert_eq!((r(7, 2).floor(), r(7, 2).ceil()), (3, 4));
+        assert_eq!((r(-7, 2).floor(), r(-7, 2).ceil()), (-4, -3));
+        assert_eq!((r(4, 2).floor(), r(4, 2).ceil()), (2, 2));
+        assert_eq!(r(4, 2).to_integer(), Some(2));
+        assert_eq!(r(5, 2).to_integer(), None);
+    }
+
+    #[test]
+    fn orders_without_overflow() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert!(r(5, 3) > r(3, 2));
+        assert_eq!(r(2, 4).cmp(&r(1, 2)), Ordering::Equal);
+
+        let (a, b) = (
+            Ratio::<i64>::new(i64::MAX - 1, i64::MAX),
+            Ratio::<i64>::new(i64::MAX - 2, i64::MAX - 1),
+        );
+        assert!(a > b);
+
+        let mut sorted = vec![r(1, 2), r(-2, 1), r(1, 3), r(7, 3)];
+        sorted.sort();
+        assert_eq!(sorted, vec![r(-2, 1), r(1, 3), r(1, 2), r(7, 3)]);
+    }
+
+    #[test]
+    fn parses_fractions() {
+        assert_eq!("3/-6".parse::<Ratio>().unwrap(), r(-1, 2));
+        assert_eq!(" 4 ".parse::<Ratio>().unwrap(), Ratio::from(4));
+
+        let error = "1/0".parse::<Ratio>().unwrap_err();
+        assert_eq!(error.column, 3);
+        assert!("a/2".parse::<Ratio>().is_err());
+    }
+}
@@ -0,0 +1,354 @@
+//! Flood fills and connected regions of a [`Grid`].
+
+use std::collections::{HashMap, HashSet};
+
+use super::{Coord, Direction, Grid, UnionFind};
+
+impl<T> Grid<T> {
+    /// Every cell reachable from `start` through orthogonal steps onto cells
+    /// that are `passable`, including `start` if it is passable itself.
+    pub fn flood_from(&self, start: Coord, passable: impl Fn(&T) -> bool) -> HashSet<Coord> {
+        let mut reached = HashSet::new();
+        let mut stack = vec![start];
+
+        while let Some(coord) = stack.pop() {
+            if !self.get(coord).is_some_and(&passable) || !reached.insert(coord) {
+                continue;
+            }
+
+            stack.extend(coord.adjacent());
+        }
+
+        reached
+    }
+
+    /// Splits the grid into regions, where neighbouring cells `a` and `b` are
+    /// in the same region when `same(a, b)`.
+    pub fn components(&self, same: impl Fn(&T, &T) -> bool) -> Components {
+        let mut labels = Grid::<Option<usize>>::with_dimensions(self.width, self.height);
+        let mut regions = vec![];
+
+        for start in self.iter_coords() {
+            if labels[start].is_some() {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut stats = Stats {
+                area: 0,
+                perimeter: 0,
+                top_left: start,
+                bottom_right: start,
+            };
+            labels[start] = Some(label);
+            let mut stack = vec![start];
+
+            while let Some(coord) = stack.pop() {
+                stats.area += 1;
+                stats.top_left =
+                    Coord(stats.top_left.0.min(coord.0), stats.top_left.1.min(coord.1));
+                stats.bottom_right = Coord(
+                    stats.bottom_right.0.max(coord.0),
+                    stats.bottom_right.1.max(coord.1),
+                );
+
+                for next in coord.adjacent() {
+                    if labels.get(next) == Some(&None) && same(&self[coord], &self[next]) {
+                        labels[next] = Some(label);
+                        stack.push(next);
+                    }
+                }
+            }
+
+            regions.push(stats);
+        }
+
+        let labels = labels.map(|label| label.unwrap());
+        for coord in labels.iter_coords() {
+            let label = labels[coord];
+            regions[label].perimeter += coord
+                .adjacent()
+                .into_iter()
+                .filter(|&next| labels.get(next) != Some(&label))
+                .count();
+        }
+
+        Components { labels, regions }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Stats {
+    area: usize,
+    perimeter: usize,
+    top_left: Coord,
+    bottom_right: Coord,
+}
+
+/// The regions found by [`Grid::components`].
+#[derive(Debug, Clone)]
+pub struct Components {
+    labels: Grid<usize>,
+    regions: Vec<Stats>,
+}
+
+impl Components {
+    /// The grid of which region each cell is in, numbered from 0 in the order
+    /// their first cells come row by row.
+    pub fn labels(&self) -> &Grid<usize> {
+        &self.labels
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    pub fn region(&self, label: usize) -> Option<Region<'_>> {
+        let stats = self.regions.get(label)?;
+
+        Some(Region {
+            labels: &self.labels,
+            label,
+            area: stats.area,
+            perimeter: stats.perimeter,
+            top_left: stats.top_left,
+            bottom_right: stats.bottom_right,
+        })
+    }
+
+    /// The region containing `coord`.
+    pub fn region_at(&self, coord: Coord) -> Option<Region<'_>> {
+        self.region(*self.labels.get(coord)?)
+    }
+
+    pub fn regions(&self) -> impl Iterator<Item = Region<'_>> {
+        (0..self.len()).flat_map(|label| self.region(label))
+    }
+}
+
+/// One connected region of a grid.
+#[derive(Debug, Clone, Copy)]
+pub struct Region<'a> {
+    labels: &'a Grid<usize>,
+    pub label: usize,
+    pub area: usize,
+    /// The number of cell edges between the region and anything else,
+    /// including the outside of the grid.
+    pub perimeter: usize,
+    /// The corners of the region's bounding box, both inclusive.
+    pub top_left: Coord,
+    pub bottom_right: Coord,
+}
+
+impl Region<'_> {
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.labels.get(coord) == Some(&self.label)
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = Coord> + '_ {
+        let (Coord(x0, y0), Coord(x1, y1)) = (self.top_left, self.bottom_right);
+
+        (y0..=y1)
+            .flat_map(move |y| (x0..=x1).map(move |x| Coord(x, y)))
+            .filter(|&coord| self.contains(coord))
+    }
+
+    /// The number of straight sides around the region, counting those of any
+    /// holes in it. A polygon has as many sides as corners, so this counts
+    /// corners instead.
+    pub fn side_count(&self) -> usize {
+        self.cells()
+            .map(|cell| {
+                Direction::all_directions()
+                    .into_iter()
+                    .filter(|&dir| {
+                        let across = dir.turn_right();
+                        let ahead = self.contains(cell.move_(dir));
+                        let beside = self.contains(cell.move_(across));
+
+                        // Either an outside corner, or an inside one where the
+                        // region wraps round a cell that isn't part of it.
+                        (!ahead && !beside)
+                            || (ahead && beside && !self.contains(cell.move_(dir).move_(across)))
+                    })
+                    .count()
+            })
+            .sum()
+    }
+}
+
+/// Given `events`, cells that become impassable one after another, finds the
+/// index of the first event after which `start` and `end` are no longer
+/// connected through `passable` cells. `None` if no event disconnects them,
+/// including when they weren't connected to begin with.
+///
+/// Works backwards from every event having happened, unblocking cells and
+/// joining them up until the two ends meet, so it takes about linear time.
+pub fn first_disconnecting<T>(
+    grid: &Grid<T>,
+    passable: impl Fn(&T) -> bool,
+    start: Coord,
+    end: Coord,
+    events: &[Coord],
+) -> Option<usize> {
+    let index = |Coord(x, y): Coord| y as usize * grid.width() + x as usize;
+
+    // A cell blocked twice only opens up again when undoing the first.
+    let mut first_blocked = HashMap::new();
+    for (i, &coord) in events.iter().enumerate() {
+        if grid.contains(coord) {
+            first_blocked.entry(coord).or_insert(i);
+        }
+    }
+
+    let mut open = grid.map(&passable);
+    for &coord in first_blocked.keys() {
+        open[coord] = false;
+    }
+
+    let mut sets = UnionFind::new(grid.width() * grid.height());
+    let join = |sets: &mut UnionFind, open: &Grid<bool>, coord: Coord| {
+        for next in coord.adjacent() {
+            if open.get(next) == Some(&true) {
+                sets.union(index(coord), index(next));
+            }
+        }
+    };
+
+    for coord in open.iter_coords().filter(|&coord| open[coord]) {
+        join(&mut sets, &open, coord);
+    }
+
+    let connected = |sets: &mut UnionFind, open: &Grid<bool>| {
+        open.get(start) == Some(&true)
+            && open.get(end) == Some(&true)
+            && sets.connected(index(start), index(end))
+    };
+    if connected(&mut sets, &open) {
+        return None;
+    }
+
+    for (i, &coord) in events.iter().enumerate().rev() {
+        if first_blocked.get(&coord) != Some(&i) || !passable(&grid[coord]) {
+            continue;
+        }
+
+        open[coord] = true;
+        join(&mut sets, &open, coord);
+        if connected(&mut sets, &open) {
+            return Some(i);
+        }
+    }
+
+    None
+}
+
+/// The same as [`first_disconnecting`], but by binary search over how many
+/// events have happened, flood filling each time. Slower, but it doesn't
+/// need every event up front to be worked backwards.
+pub fn first_disconnecting_by_search<T>(
+    grid: &Grid<T>,
+    passable: impl Fn(&T) -> bool,
+    start: Coord,
+    end: Coord,
+    events: &[Coord],
+) -> Option<usize> {
+    let connected_after = |happened: usize| {
+        let mut open = grid.map(&passable);
+        for &coord in &events[..happened] {
+            if open.contains(coord) {
+                open[coord] = false;
+            }
+        }
+
+        open.flood_from(start, |&open| open).contains(&end)
+    };
+
+    if !connected_after(0) || connected_after(events.len()) {
+        return None;
+    }
+
+    // Connected after `low` events, but not after `high`.
+    let (mut low, mut high) = (0, events.len());
+    while high - low > 1 {
+        let mid = (low + high) / 2;
+        if connected_after(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Some(high - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::parse_char_grid;
+
+    #[test]
+    fn labels_components() {
+        let grid = parse_char_grid("AAB\nABB\nCCB\n");
+        let components = grid.components(|a, b| a == b);
+
+        assert_eq!(components.len(), 3);
+        assert_eq!(components.labels().row(1), &[0, 1, 1]);
+
+        let b = components.region_at(Coord(2, 0)).unwrap();
+        assert_eq!((b.area, b.perimeter), (4, 10));
+        assert_eq!((b.top_left, b.bottom_right), (Coord(1, 0), Coord(2, 2)));
+        assert_eq!(b.side_count(), 8);
+        assert_eq!(b.cells().count(), 4);
+    }
+
+    #[test]
+    fn counts_sides_around_holes() {
+        let grid = parse_char_grid("AAAA\nABBA\nAAAA\n");
+        let components = grid.components(|a, b| a == b);
+
+        let a = components.region_at(Coord(0, 0)).unwrap();
+        assert_eq!((a.area, a.perimeter), (10, 20));
+        assert_eq!(a.side_count(), 8);
+    }
+
+    type Finder = fn(&Grid<char>, fn(&char) -> bool, Coord, Coord, &[Coord]) -> Option<usize>;
+
+    fn is_open(c: &char) -> bool {
+        *c == '.'
+    }
+
+    #[test]
+    fn finds_first_disconnecting_event() {
+        let grid = parse_char_grid("...\n.#.\n...\n");
+        let events = [
+            Coord(1, 0),
+            Coord(5, 5),
+            Coord(1, 0),
+            Coord(2, 1),
+            Coord(0, 2),
+        ];
+        let (start, end) = (Coord(0, 0), Coord(2, 2));
+
+        let finders: [Finder; 2] = [first_disconnecting, first_disconnecting_by_search];
+        for find in finders {
+            assert_eq!(find(&grid, is_open, start, end, &events), Some(4));
+            assert_eq!(find(&grid, is_open, start, end, &events[..4]), None);
+            assert_eq!(find(&grid, is_open, start, end, &[start]), Some(0));
+            assert_eq!(find(&grid, is_open, start, Coord(1, 1), &events), None);
+        }
+    }
+
+    #[test]
+    fn floods_passable_cells() {
+        let grid = parse_char_grid("..#\n#.#\n..#\n");
+
+        assert_eq!(grid.flood_from(Coord(0, 0), |&c| c == '.').len(), 5);
+        assert!(grid.flood_from(Coord(2, 0), |&c| c == '.').is_empty());
+        assert!(grid.flood_from(Coord(-1, 0), |&c| c == '.').is_empty());
+    }
+}
@@ -0,0 +1,349 @@
+//! Shortest paths over any graph, given as a `neighbors` function from each
+//! state to the states one step on from it.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Everything a search learned about the states it reached.
+#[derive(Debug, Clone)]
+pub struct Paths<S> {
+    /// The cost of the cheapest path from the start to each reached state.
+    pub distances: HashMap<S, usize>,
+    /// For each state, every state just before it on some cheapest path, so
+    /// that together they make up the DAG of all shortest paths.
+    pub predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Hash + Eq + Clone> Paths<S> {
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.distances.get(state).copied()
+    }
+
+    /// One cheapest path from the start to `end`, including both.
+    pub fn path_to(&self, end: &S) -> Option<Vec<S>> {
+        self.distances.get(end)?;
+
+        let mut path = vec![end.clone()];
+        while let Some(previous) = self
+            .predecessors
+            .get(path.last().unwrap())
+            .and_then(|states| states.first())
+        {
+            path.push(previous.clone());
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Every state on any cheapest path from the start to one of `ends`.
+    pub fn on_shortest_paths(&self, ends: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        let mut stack = ends
+            .into_iter()
+            .filter(|end| self.distances.contains_key(end))
+            .collect::<Vec<_>>();
+
+        while let Some(state) = stack.pop() {
+            if !seen.insert(state.clone()) {
+                continue;
+            }
+
+            if let Some(previous) = self.predecessors.get(&state) {
+                stack.extend(previous.iter().cloned());
+            }
+        }
+
+        seen
+    }
+}
+
+impl<S: Hash + Eq + Clone> Paths<S> {
+    fn new(start: S) -> Self {
+        Paths {
+            distances: HashMap::from([(start, 0)]),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    /// Records reaching `next` from `state` at `cost`, returning whether it is
+    /// the cheapest way there so far.
+    fn relax(&mut self, state: &S, next: S, cost: usize) -> bool {
+        match self.distances.get(&next) {
+            Some(&best) if best < cost => false,
+            Some(&best) if best == cost => {
+                // Over a zero-cost step `next` may already lead to `state`, and
+                // recording it would make the predecessors loop.
+                if !self.leads_to(&next, state, cost) {
+                    self.predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                }
+                false
+            }
+            _ => {
+                self.distances.insert(next.clone(), cost);
+                self.predecessors.insert(next, vec![state.clone()]);
+                true
+            }
+        }
+    }
+
+    /// Whether `to` is reached from `from`, or is `from`, along the shortest
+    /// paths, where `from` is at `cost`. Only states at the same cost can be
+    /// in between, so the walk stops at any cheaper one.
+    fn leads_to(&self, from: &S, to: &S, cost: usize) -> bool {
+        let mut seen = HashSet::new();
+        let mut stack = vec![to];
+
+        while let Some(state) = stack.pop() {
+            if state == from {
+                return true;
+            }
+            if !seen.insert(state) {
+                continue;
+            }
+
+            if let Some(previous) = self.predecessors.get(state) {
+                stack.extend(
+                    previous
+                        .iter()
+                        .filter(|previous| self.distances.get(*previous) == Some(&cost)),
+                );
+            }
+        }
+
+        false
+    }
+}
+
+/// Searches outwards from `start` where every step costs 1.
+pub fn bfs<S, I>(start: S, mut neighbors: impl FnMut(&S) -> I) -> Paths<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        let cost = paths.distances[&state] + 1;
+
+        for next in neighbors(&state) {
+            if paths.relax(&state, next.clone(), cost) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    paths
+}
+
+/// A state waiting in the heap, ordered so that the cheapest comes out first.
+struct Entry<S> {
+    priority: usize,
+    cost: usize,
+    state: S,
+}
+
+impl<S> PartialEq for Entry<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S> Eq for Entry<S> {}
+
+impl<S> Ord for Entry<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, since `BinaryHeap` is a max-heap.
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S> PartialOrd for Entry<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Finds the cheapest path from `start` to every state reachable from it,
+/// where `neighbors` gives each next state along with the cost of the step.
+pub fn dijkstra<S, I>(start: S, mut neighbors: impl FnMut(&S) -> I) -> Paths<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut heap = BinaryHeap::from([Entry {
+        priority: 0,
+        cost: 0,
+        state: start,
+    }]);
+
+    while let Some(Entry { cost, state, .. }) = heap.pop() {
+        if paths.distances[&state] < cost {
+            // Already popped more cheaply.
+            continue;
+        }
+
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            if paths.relax(&state, next.clone(), next_cost) {
+                heap.push(Entry {
+                    priority: next_cost,
+                    cost: next_cost,
+                    state: next,
+                });
+            }
+        }
+    }
+
+    paths
+}
+
+/// Finds the cheapest path from `start` to the first state that `is_goal`,
+/// steering by `heuristic`, which must never overestimate the cost left.
+/// Returns the cost along with the path, including both ends.
+pub fn astar<S, I>(
+    start: S,
+    mut is_goal: impl FnMut(&S) -> bool,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+) -> Option<(usize, Vec<S>)>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut heap = BinaryHeap::from([Entry {
+        priority: heuristic(&start),
+        cost: 0,
+        state: start,
+    }]);
+
+    while let Some(Entry { cost, state, .. }) = heap.pop() {
+        if paths.distances[&state] < cost {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some((cost, paths.path_to(&state)?));
+        }
+
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            if paths.relax(&state, next.clone(), next_cost) {
+                heap.push(Entry {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    state: next,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A diamond with two equally cheap routes from `a` to `d`, and a dearer
+    /// direct edge.
+    fn diamond(state: &char) -> Vec<(char, usize)> {
+        match state {
+            'a' => vec![('b', 1), ('c', 2), ('d', 5)],
+            'b' => vec![('d', 3)],
+            'c' => vec![('d', 2)],
+            'd' => vec![('e', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn finds_all_shortest_paths() {
+        let paths = dijkstra('a', diamond);
+
+        assert_eq!(paths.distance(&'d'), Some(4));
+        assert_eq!(paths.distance(&'e'), Some(5));
+        assert_eq!(paths.distance(&'z'), None);
+        assert_eq!(paths.predecessors[&'d'].len(), 2);
+        assert_eq!(
+            paths.on_shortest_paths(['e']),
+            HashSet::from(['a', 'b', 'c', 'd', 'e'])
+        );
+        assert_eq!(paths.path_to(&'a'), Some(vec!['a']));
+        assert_eq!(paths.path_to(&'e').unwrap().len(), 4);
+    }
+
+    #[test]
+    fn pops_cheapest_first() {
+        // The direct edges out of 0 are dearer than walking along the chain,
+        // so a heap that pops the most expensive entry first settles them
+        // wrongly.
+        let paths = dijkstra(0, |&n: &usize| {
+            let mut next = vec![];
+            if n < 5 {
+                next.push((n + 1, 1));
+            }
+            if n == 0 {
+                next.extend((2..=5).map(|m| (m, 10)));
+            }
+            next
+        });
+
+        assert_eq!(paths.distance(&5), Some(5));
+        assert_eq!(paths.predecessors[&5], vec![4]);
+    }
+
+    #[test]
+    fn handles_zero_cost_cycles() {
+        let cycle = |state: &char| match state {
+            'a' => vec![('b', 0), ('c', 0)],
+            'b' => vec![('a', 0), ('d', 1)],
+            'c' => vec![('b', 0)],
+            _ => vec![],
+        };
+
+        let paths = dijkstra('a', cycle);
+        assert_eq!(paths.path_to(&'b'), Some(vec!['a', 'b']));
+        assert_eq!(paths.path_to(&'d'), Some(vec!['a', 'b', 'd']));
+        assert_eq!(paths.predecessors.get(&'a'), None);
+        // Reaching `b` through `c` is just as cheap, and doesn't loop.
+        assert_eq!(paths.predecessors[&'b'].len(), 2);
+        assert_eq!(
+            paths.on_shortest_paths(['d']),
+            HashSet::from(['a', 'b', 'c', 'd'])
+        );
+
+        assert_eq!(
+            astar('a', |&s| s == 'd', cycle, |_| 0),
+            Some((1, vec!['a', 'b', 'd']))
+        );
+    }
+
+    #[test]
+    fn searches_breadth_first() {
+        let paths = bfs(0, |&n: &i32| {
+            [n + 1, n * 2].into_iter().filter(|&m| m <= 20)
+        });
+
+        assert_eq!(paths.distance(&20), Some(6));
+        assert_eq!(paths.path_to(&20).unwrap().first(), Some(&0));
+    }
+
+    #[test]
+    fn finds_goal_with_astar() {
+        let (cost, path) = astar('a', |&s| s == 'e', diamond, |_| 0).unwrap();
+
+        assert_eq!(cost, 5);
+        assert_eq!(path.first(), Some(&'a'));
+        assert_eq!(path.last(), Some(&'e'));
+        assert_eq!(astar('b', |&s| s == 'a', diamond, |_| 0), None);
+    }
+}
@@ -0,0 +1,105 @@
+/// A disjoint-set forest over `0..len`, with path compression and union by
+/// rank, so that any sequence of operations is as good as linear.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// `len` elements, each in a set of its own.
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            sets: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The number of distinct sets.
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// The representative of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Point everything on the way straight at the root.
+        let mut curr = x;
+        while self.parent[curr] != root {
+            curr = std::mem::replace(&mut self.parent[curr], root);
+        }
+
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returning whether they were
+    /// separate.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (child, root) = if self.rank[a] < self.rank[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[child] = root;
+        if self.rank[child] == self.rank[root] {
+            self.rank[root] += 1;
+        }
+        self.sets -= 1;
+
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_sets() {
+        let mut sets = UnionFind::new(6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert!(sets.connected(0, 3));
+        assert!(!sets.connected(0, 4));
+        assert_eq!(sets.set_count(), 3);
+    }
+
+    #[test]
+    fn compresses_long_chains() {
+        let mut sets = UnionFind::new(1000);
+        for i in 1..1000 {
+            sets.union(i - 1, i);
+        }
+
+        let root = sets.find(999);
+        assert!((0..1000).all(|i| sets.find(i) == root));
+        assert_eq!(sets.set_count(), 1);
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{output::DayOutput, table::format_duration};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification time of every file under `paths`, so that changes can be
+/// spotted by comparing one snapshot with the next.
+fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    let mut files = BTreeMap::new();
+    let mut pending = paths.to_vec();
+
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                pending.extend(entries.filter_map(|entry| Some(entry.ok()?.path())));
+            }
+        } else if let Ok(modified) = metadata.modified() {
+            files.insert(path, modified);
+        }
+    }
+
+    files
+}
+
+/// Rebuilds the binary with the same profile and features as this one,
+/// leaving cargo to print any compile errors.
+fn rebuild() -> Result<(), String> {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let mut cargo = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cargo
+        .args(["build", "--quiet", "--manifest-path"])
+        .arg(manifest);
+    if env!("BUILD_PROFILE") == "release" {
+        cargo.arg("--release");
+    }
+    if cfg!(feature = "alloc-profile") {
+        cargo.args(["--features", "alloc-profile"]);
+    }
+
+    match cargo.status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(_) => Err("build failed".to_string()),
+        Err(e) => Err(format!("could not run cargo: {}", e)),
+    }
+}
+
+/// Runs the day in the freshly built binary at `exe`, which reports back as
+/// JSON.
+fn rerun(exe: &Path, year: usize, day: usize, input: Option<&Path>) -> Result<DayOutput, String> {
+    let mut command = Command::new(exe);
+    command.args([
+        day.to_string(),
+        "--year".to_string(),
+        year.to_string(),
+        "--format".to_string(),
+        "json".to_string(),
+    ]);
+    if let Some(input) = input {
+        command.arg("--input").arg(input);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("could not run day {}: {}", day, e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    serde_json::from_str(stdout.trim())
+        .map_err(|e| format!("unexpected output from day {}: {}", day, e))
+}
+
+/// Describes one part's answer, noting what it was on the previous run if it
+/// has changed since.
+fn answer_line(part: usize, answer: Option<&str>, previous: Option<Option<&str>>) -> String {
+    let line = format!("Part {}: {}", part, answer.unwrap_or("-"));
+
+    match previous {
+        Some(previous) if previous != answer => {
+            format!("{} (was {})", line, previous.unwrap_or("-"))
+        }
+        _ => line,
+    }
+}
+
+fn report(output: &DayOutput, previous: Option<&DayOutput>) {
+    if let Some(e) = &output.error {
+        eprintln!("Day {} failed: {}", output.day, e);
+        return;
+    }
+
+    println!(
+        "{}",
+        answer_line(
+            1,
+            output.part1.as_deref(),
+            previous.map(|p| p.part1.as_deref())
+        )
+    );
+    println!(
+        "{}",
+        answer_line(
+            2,
+            output.part2.as_deref(),
+            previous.map(|p| p.part2.as_deref())
+        )
+    );
+
+    if let Some(timings) = &output.timings {
+        println!(
+            "Took {} (parse {}, part 1 {}, part 2 {})",
+            format_duration(Duration::from_nanos(timings.total_ns)),
+            format_duration(Duration::from_nanos(timings.parse_ns)),
+            format_duration(Duration::from_nanos(timings.part1_ns)),
+            format_duration(Duration::from_nanos(timings.part2_ns))
+        );
+    }
+}
+
+/// Polls `paths` and reruns the day every time anything under them changes,
+/// until interrupted.
+pub fn watch(year: usize, day: usize, paths: Vec<PathBuf>, input: Option<PathBuf>) -> ! {
+    // Looked up before the first rebuild replaces the file, after which the
+    // running binary's path no longer exists.
+    let exe = env::current_exe().unwrap_or_else(|e| {
+        eprintln!("Could not find this binary: {}", e);
+        std::process::exit(1)
+    });
+    println!(
+        "Watching {} for changes",
+        paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let mut seen = None;
+    let mut previous = None;
+
+    loop {
+        let current = snapshot(&paths);
+        if seen.as_ref() != Some(&current) {
+            seen = Some(current);
+
+            println!();
+            println!("Day {}", day);
+            match rebuild().and_then(|_| rerun(&exe, year, day, input.as_deref())) {
+                Ok(output) => {
+                    report(&output, previous.as_ref());
+                    if output.error.is_none() {
+                        previous = Some(output);
+                    }
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_changed_answers() {
+        assert_eq!(answer_line(1, Some("11"), None), "Part 1: 11");
+        assert_eq!(answer_line(1, Some("11"), Some(Some("11"))), "Part 1: 11");
+        assert_eq!(
+            answer_line(2, Some("31"), Some(Some("30"))),
+            "Part 2: 31 (was 30)"
+        );
+        assert_eq!(answer_line(2, Some("31"), Some(None)), "Part 2: 31 (was -)");
+    }
+
+    #[test]
+    fn snapshots_spot_changes() {
+        let dir = env::temp_dir().join(format!("aoc-2024-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("nested/input.in"), "1").unwrap();
+
+        let paths = [dir.clone()];
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 1);
+
+        fs::write(dir.join("mod.rs"), "").unwrap();
+        assert_ne!(snapshot(&paths), before);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
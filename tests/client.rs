@@ -0,0 +1,146 @@
+mod common;
+
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use aoc_2024::{
+    client::{Client, Config, Error, Fetched, USER_AGENT},
+    submit::Outcome,
+};
+use common::MockServer;
+
+fn client(server: &MockServer) -> Client {
+    Client::new(Config {
+        base_url: Some(server.base_url.clone()),
+        session: Some("abc123".to_string()),
+    })
+    .unwrap()
+    .with_min_interval(Duration::ZERO)
+}
+
+fn scratch_path(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-2024-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    dir.join("input.in")
+}
+
+#[test]
+fn downloads_input_with_session_and_user_agent() {
+    let server = MockServer::start(vec![(200, vec![], "1 2\n3 4\n")]);
+
+    assert_eq!(client(&server).input(2024, 5).unwrap(), "1 2\n3 4\n");
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/2024/day/5/input");
+    assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+    assert_eq!(requests[0].header("User-Agent"), Some(USER_AGENT));
+}
+
+#[test]
+fn never_refetches_a_cached_input() {
+    let server = MockServer::start(vec![(200, vec![], "input\n"), (200, vec![], "other\n")]);
+    let client = client(&server);
+    let path = scratch_path("cache");
+
+    assert_eq!(
+        client.fetch_input(2024, 1, &path).unwrap(),
+        Fetched::Downloaded
+    );
+    assert_eq!(client.fetch_input(2024, 1, &path).unwrap(), Fetched::Cached);
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "input\n");
+    assert_eq!(server.requests().len(), 1);
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn reports_rate_limits_and_errors() {
+    let server = MockServer::start(vec![
+        (429, vec![("Retry-After", "30")], ""),
+        (404, vec![], "Not found\n"),
+    ]);
+    let client = client(&server);
+
+    assert!(matches!(
+        client.input(2024, 1),
+        Err(Error::RateLimited(Some(30)))
+    ));
+    assert_eq!(
+        client.input(2024, 26).unwrap_err().to_string(),
+        "server responded 404: Not found"
+    );
+}
+
+#[test]
+fn spaces_out_requests() {
+    let server = MockServer::start(vec![(200, vec![], ""), (200, vec![], "")]);
+    let client = client(&server).with_min_interval(Duration::from_millis(200));
+
+    let start = Instant::now();
+    client.input(2024, 1).unwrap();
+    client.input(2024, 2).unwrap();
+
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+#[test]
+fn requires_a_session() {
+    let config = Config {
+        base_url: None,
+        session: None,
+    };
+
+    assert!(matches!(Client::new(config), Err(Error::MissingSession)));
+}
+
+#[test]
+fn submits_answers() {
+    let server = MockServer::start(vec![
+        (
+            200,
+            vec![],
+            "<p>That's not the right answer; your answer is too high.</p>",
+        ),
+        (200, vec![], "<p>That's the right answer!</p>"),
+    ]);
+    let client = client(&server);
+
+    assert_eq!(client.submit(2024, 3, 2, "1234").unwrap(), Outcome::TooHigh);
+    assert_eq!(client.submit(2024, 3, 2, "1000").unwrap(), Outcome::Correct);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2024/day/3/answer");
+    assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+    assert_eq!(requests[0].body, "level=2&answer=1234");
+}
+
+#[test]
+fn reports_answering_too_soon() {
+    let server = MockServer::start(vec![
+        (
+            200,
+            vec![],
+            "<p>You gave an answer too recently; you have to wait after submitting an answer \
+            before trying again.  You have 1m 5s left to wait.</p>",
+        ),
+        (200, vec![], "<html>Something else</html>"),
+    ]);
+    let client = client(&server);
+
+    assert!(matches!(
+        client.submit(2024, 1, 1, "5"),
+        Err(Error::RateLimited(Some(65)))
+    ));
+    assert!(matches!(
+        client.submit(2024, 1, 1, "5"),
+        Err(Error::Unrecognised(_))
+    ));
+}
@@ -0,0 +1,73 @@
+use aoc_2024::{
+    day07::Day07,
+    day11::Day11,
+    solution::{Answer, Solution},
+    util::{parse_char_grid, Coord, Counter},
+};
+
+const DAY01_EXAMPLE: &str = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+const DAY07_EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+#[test]
+fn solves_days_directly() {
+    let input = Day07::parse(DAY07_EXAMPLE).unwrap();
+
+    assert_eq!(Day07::part1(&input), Answer::Number(3749));
+    assert_eq!(Day07::part2(&input), Answer::Number(11387));
+
+    let input = Day11::parse("125 17").unwrap();
+
+    assert_eq!(Day11::part1(&input), Answer::Number(55312));
+}
+
+#[test]
+fn runs_days_from_the_registry() {
+    let report = aoc_2024::day(2024, 1).unwrap().run(DAY01_EXAMPLE).unwrap();
+
+    assert_eq!(report.part1, Answer::Number(11));
+    assert_eq!(report.part2, Answer::Number(31));
+    assert!(aoc_2024::day(2024, 26).is_none());
+}
+
+#[test]
+fn reports_invalid_input() {
+    let error = aoc_2024::day(2024, 1)
+        .unwrap()
+        .run("3   4\n4   x\n")
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        "invalid input for day 1 at line 2, column 5: expected a number, found `x`"
+    );
+}
+
+#[test]
+fn exposes_utilities() {
+    let grid = parse_char_grid("ab\ncd\n");
+
+    assert_eq!(grid.get(Coord(1, 1)), Some(&'d'));
+
+    let counter = "hello".chars().collect::<Counter<_>>();
+
+    assert_eq!(counter.get(&'l'), 2);
+    assert_eq!(counter.total(), 5);
+}